@@ -2,6 +2,8 @@ use std::collections::HashSet;
 
 /// Example inspired from mvines's [rust-jup-ag](https://github.com/mvines/rust-jup-ag/blob/master/examples/swap.rs)
 use itertools::Itertools;
+use juno::mint::{amount_to_ui_amount, ui_amount_to_amount, MintDecimalsCache};
+use juno::reexports::Decimal;
 use juno::DecompiledVersionedTx;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
@@ -10,7 +12,6 @@ use solana_sdk::{
     pubkey::Pubkey,
     signature::{read_keypair_file, Keypair, Signer},
 };
-use spl_token::{amount_to_ui_amount, ui_amount_to_amount};
 
 const USDH: Pubkey = pubkey!("USDH1SM1ojwWUga67PGrgFWUHibbjqMvuMaDkRJTgkX");
 const HBB: Pubkey = pubkey!("HBB111SCo9jkCejsZfz8Ec8nH7T6THF8KEKSnvwT6XK6");
@@ -39,6 +40,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let hbb_ata =
         spl_associated_token_account::get_associated_token_address(&keypair.pubkey(), &HBB);
 
+    let mint_decimals = MintDecimalsCache::new();
+    let decimals = mint_decimals
+        .get_or_fetch(&[USDH, HBB], &rpc_client)
+        .await?;
+    let (usdh_decimals, hbb_decimals) = (decimals[0], decimals[1]);
+
     println!(
         "Pre-swap USDH balance: {}",
         amount_to_ui_amount(
@@ -47,8 +54,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .await?
                 .amount
                 .parse::<u64>()?,
-            6
-        )
+            usdh_decimals
+        )?
     );
 
     println!(
@@ -59,20 +66,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .await?
                 .amount
                 .parse::<u64>()?,
-            6
-        )
+            hbb_decimals
+        )?
     );
 
     // set Jup base URL
     juno::set_base_url("https://quote-api.jup.ag".to_string())?;
 
     let quote: juno::SwapRoute = juno::get_quote(
-        &USDH,
-        &HBB,
-        ui_amount_to_amount(1.0, 6),
-        false,
-        Some(100),
-        Some(20),
+        &juno::QuoteRequest::new(USDH, HBB, ui_amount_to_amount(Decimal::ONE, usdh_decimals)?)
+            .slippage_bps(100)
+            .max_accounts(20),
     )
     .await?;
 
@@ -83,14 +87,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .join(", ");
     println!(
         "Quote: {} USDH for {} HBB via {} (worst case with slippage: {}). Impact pct: {}%",
-        amount_to_ui_amount(quote.in_amount, 6),
-        amount_to_ui_amount(quote.out_amount, 6),
+        quote.ui_in_amount(usdh_decimals)?,
+        quote.ui_out_amount(hbb_decimals)?,
         route,
-        amount_to_ui_amount(quote.other_amount_threshold, 6),
+        amount_to_ui_amount(quote.other_amount_threshold, hbb_decimals)?,
         quote.price_impact_pct
     );
 
-    let decompiled_ixs = juno::get_swap_instructions(quote, keypair.pubkey(), &rpc_client).await?;
+    let decompiled_ixs = juno::get_swap_instructions(
+        quote,
+        keypair.pubkey(),
+        &juno::SwapOptions::default(),
+        &rpc_client,
+    )
+    .await?;
 
     println!("Swap ixs received: {decompiled_ixs:#?}");
 
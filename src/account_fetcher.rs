@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use itertools::Itertools;
+use solana_address_lookup_table_program::state::AddressLookupTable;
+use solana_client::rpc_request::MAX_MULTIPLE_ACCOUNTS;
+use solana_program::hash::Hash;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use solana_sdk::clock::Slot;
+
+use crate::errors::{Error, Result};
+use crate::utils::AsyncAccountFetcher;
+
+/// Default time a lookup table is served from the cache
+pub const DEFAULT_LOOKUP_TABLE_TTL: Duration = Duration::from_secs(60);
+
+/// `AsyncAccountFetcher` wrapper deduplicating keys, splitting requests in batches the RPC
+/// accepts and caching lookup tables
+///
+/// Only lookup tables are cached: they are append only, a cached copy can miss addresses
+/// added since but never holds wrong ones. Copies are served until their TTL expires, whatever
+/// the requested slot, and decompiling refetches a table once through `refetch_accounts`,
+/// which bypasses the cache, when a lookup index is past its cached addresses. Tables with a
+/// deactivation slot are never cached as they are about to be closed.
+#[derive(Debug)]
+pub struct CachingAccountFetcher<F> {
+    inner: F,
+    batch_size: usize,
+    lookup_table_ttl: Duration,
+    lookup_tables: RwLock<HashMap<Pubkey, CachedLookupTable>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug, Clone)]
+struct CachedLookupTable {
+    account: Account,
+    fetched_at: Instant,
+}
+
+impl<F> CachingAccountFetcher<F> {
+    pub fn new(inner: F) -> Self {
+        Self {
+            inner,
+            batch_size: MAX_MULTIPLE_ACCOUNTS,
+            lookup_table_ttl: DEFAULT_LOOKUP_TABLE_TTL,
+            lookup_tables: RwLock::default(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Maximum number of accounts per request to the inner fetcher, capped to the RPC limit
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.clamp(1, MAX_MULTIPLE_ACCOUNTS);
+        self
+    }
+
+    pub fn with_lookup_table_ttl(mut self, lookup_table_ttl: Duration) -> Self {
+        self.lookup_table_ttl = lookup_table_ttl;
+        self
+    }
+
+    pub fn inner(&self) -> &F {
+        &self.inner
+    }
+
+    /// Drop a lookup table from the cache, e.g. when a lookup index is past its cached addresses
+    pub fn invalidate(&self, pubkey: &Pubkey) {
+        self.write().remove(pubkey);
+    }
+
+    pub fn clear(&self) {
+        self.write().clear();
+    }
+
+    /// Number of lookup tables served from the cache
+    pub fn cache_hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of accounts fetched with the inner fetcher
+    pub fn cache_misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn cached(&self, pubkey: &Pubkey) -> Option<Account> {
+        self.read()
+            .get(pubkey)
+            .filter(|table| table.fetched_at.elapsed() < self.lookup_table_ttl)
+            .map(|table| table.account.clone())
+    }
+
+    fn cache_if_lookup_table(&self, pubkey: Pubkey, account: &Account) {
+        if account.owner != solana_address_lookup_table_program::id() {
+            return;
+        }
+        let Ok(table) = AddressLookupTable::deserialize(&account.data) else {
+            return;
+        };
+        let mut lookup_tables = self.write();
+        if table.meta.deactivation_slot != Slot::MAX {
+            lookup_tables.remove(&pubkey);
+            return;
+        }
+        let cached = CachedLookupTable {
+            account: account.clone(),
+            fetched_at: Instant::now(),
+        };
+        lookup_tables.insert(pubkey, cached);
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<Pubkey, CachedLookupTable>> {
+        self.lookup_tables.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<Pubkey, CachedLookupTable>> {
+        self.lookup_tables
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<F: AsyncAccountFetcher + Send> CachingAccountFetcher<F> {
+    /// `min_context_slot` is `None` to bypass the cache
+    async fn fetch(
+        &self,
+        pubkeys: &[Pubkey],
+        min_context_slot: Option<Slot>,
+    ) -> Result<Vec<Option<Account>>> {
+        let mut accounts = pubkeys
+            .iter()
+            .unique()
+            .map(|pubkey| {
+                let cached = min_context_slot.and_then(|_| self.cached(pubkey));
+                (*pubkey, cached)
+            })
+            .collect::<HashMap<_, _>>();
+        let missing = accounts
+            .iter()
+            .filter(|(_, account)| account.is_none())
+            .map(|(pubkey, _)| *pubkey)
+            .collect_vec();
+        self.hits
+            .fetch_add((accounts.len() - missing.len()) as u64, Ordering::Relaxed);
+        self.misses
+            .fetch_add(missing.len() as u64, Ordering::Relaxed);
+
+        let chunks = missing.chunks(self.batch_size).collect_vec();
+        let batches = try_join_all(chunks.iter().map(|chunk| match min_context_slot {
+            Some(slot) => self.inner.fech_accounts_at_slot(chunk, slot),
+            None => self.inner.refetch_accounts(chunk),
+        }))
+        .await?;
+        for (chunk, batch) in chunks.into_iter().zip(batches) {
+            // A short batch would shift every later account onto the wrong key
+            if batch.len() != chunk.len() {
+                return Err(Error::AccountCountMismatch {
+                    requested: chunk.len(),
+                    returned: batch.len(),
+                });
+            }
+            for (pubkey, account) in chunk.iter().zip(batch) {
+                if let Some(account) = &account {
+                    self.cache_if_lookup_table(*pubkey, account);
+                }
+                accounts.insert(*pubkey, account);
+            }
+        }
+
+        Ok(pubkeys
+            .iter()
+            .map(|pubkey| accounts.get(pubkey).cloned().flatten())
+            .collect())
+    }
+}
+
+#[async_trait]
+impl<F: AsyncAccountFetcher + Send> AsyncAccountFetcher for CachingAccountFetcher<F> {
+    async fn fech_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        self.fetch(pubkeys, Some(0)).await
+    }
+
+    async fn get_latest_blockhash(&self) -> Result<Hash> {
+        self.inner.get_latest_blockhash().await
+    }
+
+    async fn fech_accounts_at_slot(
+        &self,
+        pubkeys: &[Pubkey],
+        min_context_slot: Slot,
+    ) -> Result<Vec<Option<Account>>> {
+        self.fetch(pubkeys, Some(min_context_slot)).await
+    }
+
+    async fn refetch_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        self.fetch(pubkeys, None).await
+    }
+}
@@ -8,49 +8,267 @@
  * Generated by: https://openapi-generator.tech
  */
 
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
 use reqwest;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use super::retry::{RateLimiter, RetryPolicy};
 
 pub const DEFAULT_BASE_URL: &str = "https://quote-api.jup.ag";
 
-#[derive(Debug, Clone)]
+/// Default timeout to establish a connection with the API
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default timeout of every request attempt, body included
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default number of mints per price request of the bulk price API
+pub const DEFAULT_PRICE_CHUNK_SIZE: usize = 100;
+
+/// Default number of concurrent price requests of the bulk price API
+pub const DEFAULT_PRICE_CONCURRENCY: usize = 4;
+
+/// Header carrying the `api_key` of the configuration
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Version of the price API targeted by `get_prices`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PriceApiVersion {
+    /// Legacy `/v4/price`
+    #[default]
+    V4,
+    /// `/v6/price`, same response shape as v4
+    V6,
+    /// `/price/v2`, prices as strings with optional `extraInfo`, does not take an amount
+    V2,
+}
+
+impl PriceApiVersion {
+    /// Path of the price endpoint, relative to the price base path
+    pub fn path(&self) -> &'static str {
+        match self {
+            Self::V4 => "/v4/price",
+            Self::V6 => "/v6/price",
+            Self::V2 => "/price/v2",
+        }
+    }
+}
+
+/// Credentials and extra headers are redacted from the `Debug` output
+#[derive(Clone)]
 pub struct Configuration {
     pub base_path: String,
+    /// Host serving the price API, `base_path` is used when not set
+    pub price_base_path: Option<String>,
+    pub price_api_version: PriceApiVersion,
+    /// Maximum number of mints per request of `get_prices_bulk`
+    pub price_chunk_size: usize,
+    /// Maximum number of concurrent requests of `get_prices_bulk`
+    pub price_concurrency: usize,
     pub user_agent: Option<String>,
+    /// Built with `connect_timeout`, use `with_connect_timeout` to change it
     pub client: reqwest::Client,
+    pub connect_timeout: Option<Duration>,
+    /// Timeout of every request attempt, overridden by the per-call deadlines
+    pub timeout: Option<Duration>,
     pub basic_auth: Option<BasicAuth>,
     pub oauth_access_token: Option<String>,
     pub bearer_access_token: Option<String>,
     pub api_key: Option<ApiKey>,
+    /// Headers added to every request
+    pub extra_headers: HeaderMap,
+    pub retry_policy: RetryPolicy,
+    /// Shared between all the clones of this configuration
+    pub rate_limiter: Option<Arc<RateLimiter>>,
     // TODO: take an oauth2 token source, similar to the go one
 }
 
 pub type BasicAuth = (String, Option<String>);
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ApiKey {
     pub prefix: Option<String>,
     pub key: String,
 }
 
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKey")
+            .field("prefix", &self.prefix)
+            .field("key", &REDACTED)
+            .finish()
+    }
+}
+
+const REDACTED: &str = "<redacted>";
+
+impl fmt::Debug for Configuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redact = |secret: bool| secret.then_some(REDACTED);
+        f.debug_struct("Configuration")
+            .field("base_path", &self.base_path)
+            .field("price_base_path", &self.price_base_path)
+            .field("price_api_version", &self.price_api_version)
+            .field("price_chunk_size", &self.price_chunk_size)
+            .field("price_concurrency", &self.price_concurrency)
+            .field("user_agent", &self.user_agent)
+            .field("client", &self.client)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("timeout", &self.timeout)
+            .field("basic_auth", &redact(self.basic_auth.is_some()))
+            .field(
+                "oauth_access_token",
+                &redact(self.oauth_access_token.is_some()),
+            )
+            .field(
+                "bearer_access_token",
+                &redact(self.bearer_access_token.is_some()),
+            )
+            .field("api_key", &self.api_key)
+            .field(
+                "extra_headers",
+                &self.extra_headers.keys().collect::<Vec<_>>(),
+            )
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .finish()
+    }
+}
+
 impl Default for Configuration {
     fn default() -> Self {
         Configuration {
             base_path: DEFAULT_BASE_URL.to_owned(),
+            price_base_path: None,
+            price_api_version: PriceApiVersion::default(),
+            price_chunk_size: DEFAULT_PRICE_CHUNK_SIZE,
+            price_concurrency: DEFAULT_PRICE_CONCURRENCY,
             user_agent: Some("JupiterAPI/OpenAPI/0.0.1/rust".to_owned()),
-            client: reqwest::Client::new(),
+            client: build_client(Some(DEFAULT_CONNECT_TIMEOUT)),
+            connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+            timeout: Some(DEFAULT_TIMEOUT),
             basic_auth: None,
             oauth_access_token: None,
             bearer_access_token: None,
             api_key: None,
+            extra_headers: HeaderMap::new(),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
         }
     }
 }
 
 impl Configuration {
-    pub fn new(base_path: &'static str) -> Self {
+    pub fn new(base_path: impl Into<String>) -> Self {
         Configuration {
-            base_path: base_path.to_owned(),
+            base_path: base_path.into(),
             ..Default::default()
         }
     }
+
+    pub fn with_price_base_path(mut self, price_base_path: impl Into<String>) -> Self {
+        self.price_base_path = Some(price_base_path.into());
+        self
+    }
+
+    pub fn with_price_api_version(mut self, price_api_version: PriceApiVersion) -> Self {
+        self.price_api_version = price_api_version;
+        self
+    }
+
+    /// Split bulk price requests in chunks of `chunk_size` mints, at most `concurrency` in flight
+    pub fn with_price_chunking(mut self, chunk_size: usize, concurrency: usize) -> Self {
+        self.price_chunk_size = chunk_size.max(1);
+        self.price_concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(ApiKey {
+            prefix: None,
+            key: key.into(),
+        });
+        self
+    }
+
+    pub fn with_bearer_access_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_access_token = Some(token.into());
+        self
+    }
+
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.extra_headers.insert(name, value);
+        self
+    }
+
+    /// Attach the configured credentials and extra headers to a request
+    pub fn apply_credentials(
+        &self,
+        mut req_builder: reqwest::RequestBuilder,
+    ) -> reqwest::RequestBuilder {
+        if !self.extra_headers.is_empty() {
+            req_builder = req_builder.headers(self.extra_headers.clone());
+        }
+        if let Some(ApiKey { prefix, key }) = &self.api_key {
+            let value = match prefix {
+                Some(prefix) => format!("{} {}", prefix, key),
+                None => key.clone(),
+            };
+            // An invalid key is skipped here and reported by the API as unauthorized
+            if let Ok(mut value) = HeaderValue::from_str(&value) {
+                value.set_sensitive(true);
+                req_builder = req_builder.header(API_KEY_HEADER, value);
+            }
+        }
+        // Only one `Authorization` header can be sent, the bearer token takes precedence
+        if let Some(token) = &self.bearer_access_token {
+            req_builder = req_builder.bearer_auth(token);
+        } else if let Some(token) = &self.oauth_access_token {
+            req_builder = req_builder.bearer_auth(token);
+        } else if let Some((username, password)) = &self.basic_auth {
+            req_builder = req_builder.basic_auth(username, password.as_ref());
+        }
+        req_builder
+    }
+
+    /// Rebuild the `reqwest::Client` with the given connect timeout
+    pub fn with_connect_timeout(mut self, connect_timeout: Option<Duration>) -> Self {
+        self.connect_timeout = connect_timeout;
+        self.client = build_client(connect_timeout);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Limit the requests sent with this configuration and all its clones
+    ///
+    /// Panics if `requests_per_second` is not a finite positive number.
+    pub fn with_rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(requests_per_second, burst)));
+        self
+    }
+
+    pub fn price_base_path(&self) -> &str {
+        self.price_base_path.as_deref().unwrap_or(&self.base_path)
+    }
+}
+
+fn build_client(connect_timeout: Option<Duration>) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder();
+    if let Some(connect_timeout) = connect_timeout {
+        builder = builder.connect_timeout(connect_timeout);
+    }
+    // Same failure mode as `reqwest::Client::new`
+    builder.build().expect("TLS backend cannot be initialized")
 }
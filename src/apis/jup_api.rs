@@ -8,16 +8,20 @@
  * Generated by: https://openapi-generator.tech
  */
 
+use std::time::Duration;
+
+use itertools::Itertools;
 use reqwest;
 use solana_sdk::pubkey::Pubkey;
 
-use super::{configuration, Error};
+use super::{configuration, execute, Error};
 use crate::{apis::ResponseContent, SwapRoute};
 
 /// struct for typed errors of method [`indexed_route_map_get`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum IndexedRouteMapGetError {
+    JupiterError(crate::models::ApiErrorResponse),
     UnknownValue(serde_json::Value),
 }
 
@@ -35,6 +39,7 @@ pub enum PriceGetError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum QuoteGetError {
+    JupiterError(crate::models::ApiErrorResponse),
     UnknownValue(serde_json::Value),
 }
 
@@ -42,6 +47,15 @@ pub enum QuoteGetError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum SwapPostError {
+    JupiterError(crate::models::ApiErrorResponse),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`swap_instructions_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SwapInstructionsPostError {
+    JupiterError(crate::models::ApiErrorResponse),
     UnknownValue(serde_json::Value),
 }
 
@@ -51,6 +65,7 @@ pub async fn indexed_route_map_get(
     only_direct_routes: bool,
 ) -> Result<crate::models::RouteMap, Error<IndexedRouteMapGetError>> {
     let local_var_configuration = configuration;
+    let local_var_timeout = None;
 
     let local_var_client = &local_var_configuration.client;
 
@@ -65,9 +80,10 @@ pub async fn indexed_route_map_get(
         local_var_req_builder =
             local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
     }
+    local_var_req_builder = local_var_configuration.apply_credentials(local_var_req_builder);
 
     let local_var_req = local_var_req_builder.build()?;
-    let local_var_resp = local_var_client.execute(local_var_req).await?;
+    let local_var_resp = execute(local_var_configuration, local_var_req, local_var_timeout).await?;
 
     let local_var_status = local_var_resp.status();
     let local_var_content = local_var_resp.text().await?;
@@ -86,26 +102,27 @@ pub async fn indexed_route_map_get(
     }
 }
 
-/// Get simple price for a given input mint, output mint and amount
+/// Get simple price for the given ids, vs token and amount, ids can be mints or symbols
 pub async fn prices_get(
     configuration: &configuration::Configuration,
-    ids: &[Pubkey],
-    vs_token: &Pubkey,
-    vs_amount: f32,
+    ids: &[crate::models::PriceId],
+    vs_token: &crate::models::PriceId,
+    vs_amount: rust_decimal::Decimal,
 ) -> Result<crate::models::PriceGet200Response, Error<PriceGetError>> {
     let local_var_configuration = configuration;
+    let local_var_timeout = None;
 
     let local_var_client = &local_var_configuration.client;
 
-    let local_var_uri_str = "https://quote-api.jup.ag/v4/price";
+    let local_var_uri_str = format!(
+        "{}{}",
+        local_var_configuration.price_base_path(),
+        local_var_configuration.price_api_version.path()
+    );
     let mut local_var_req_builder =
-        local_var_client.request(reqwest::Method::GET, local_var_uri_str);
+        local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
 
-    let merged_ids: String = ids
-        .iter()
-        .map(ToString::to_string)
-        .reduce(|ids, id| ids + "," + &id)
-        .unwrap();
+    let merged_ids = ids.iter().map(ToString::to_string).join(",");
     local_var_req_builder = local_var_req_builder.query(&[("ids", &merged_ids)]);
     local_var_req_builder = local_var_req_builder.query(&[("vsToken", vs_token.to_string())]);
 
@@ -114,9 +131,10 @@ pub async fn prices_get(
         local_var_req_builder =
             local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
     }
+    local_var_req_builder = local_var_configuration.apply_credentials(local_var_req_builder);
 
     let local_var_req = local_var_req_builder.build()?;
-    let local_var_resp = local_var_client.execute(local_var_req).await?;
+    let local_var_resp = execute(local_var_configuration, local_var_req, local_var_timeout).await?;
 
     let local_var_status = local_var_resp.status();
     let local_var_content = local_var_resp.text().await?;
@@ -134,51 +152,82 @@ pub async fn prices_get(
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-/// Get quote for a given input mint, output mint and amount
-pub async fn quote_get(
+/// Get the v2 price of the given ids, with details on how it was derived if `show_extra_info`
+pub async fn prices_v2_get(
     configuration: &configuration::Configuration,
-    input_mint: &Pubkey,
-    output_mint: &Pubkey,
-    amount: u64,
-    slippage_bps: Option<u16>,
-    only_direct_routes: bool,
-    as_legacy_transaction: bool,
-    max_accounts: Option<u8>,
-) -> Result<SwapRoute, Error<QuoteGetError>> {
+    ids: &[Pubkey],
+    vs_token: Option<&Pubkey>,
+    show_extra_info: bool,
+) -> Result<crate::models::PriceV2Response, Error<PriceGetError>> {
     let local_var_configuration = configuration;
+    let local_var_timeout = None;
 
     let local_var_client = &local_var_configuration.client;
 
-    let local_var_uri_str = format!("{}/quote", local_var_configuration.base_path);
+    let local_var_uri_str = format!(
+        "{}{}",
+        local_var_configuration.price_base_path(),
+        configuration::PriceApiVersion::V2.path()
+    );
     let mut local_var_req_builder =
         local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
 
-    local_var_req_builder = local_var_req_builder.query(&[("inputMint", &input_mint.to_string())]);
-    local_var_req_builder =
-        local_var_req_builder.query(&[("outputMint", &output_mint.to_string())]);
-    local_var_req_builder = local_var_req_builder.query(&[("amount", &amount.to_string())]);
-    if let Some(ref local_var_str) = slippage_bps {
-        local_var_req_builder =
-            local_var_req_builder.query(&[("slippageBps", &local_var_str.to_string())]);
-    }
-    if only_direct_routes {
-        local_var_req_builder = local_var_req_builder.query(&[("onlyDirectRoutes", "true")]);
+    let merged_ids = ids.iter().map(ToString::to_string).join(",");
+    local_var_req_builder = local_var_req_builder.query(&[("ids", &merged_ids)]);
+    if let Some(vs_token) = vs_token {
+        local_var_req_builder = local_var_req_builder.query(&[("vsToken", vs_token.to_string())]);
     }
-    if as_legacy_transaction {
-        local_var_req_builder = local_var_req_builder.query(&[("asLegacyTransaction", "true")]);
+    if show_extra_info {
+        local_var_req_builder = local_var_req_builder.query(&[("showExtraInfo", "true")]);
     }
-    if let Some(ref local_var_str) = max_accounts {
+    if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
         local_var_req_builder =
-            local_var_req_builder.query(&[("maxAccounts", &local_var_str.to_string())]);
+            local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
     }
+    local_var_req_builder = local_var_configuration.apply_credentials(local_var_req_builder);
+
+    let local_var_req = local_var_req_builder.build()?;
+    let local_var_resp = execute(local_var_configuration, local_var_req, local_var_timeout).await?;
+
+    let local_var_status = local_var_resp.status();
+    let local_var_content = local_var_resp.text().await?;
+
+    if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+        serde_json::from_str(&local_var_content).map_err(Error::from)
+    } else {
+        let local_var_entity: Option<PriceGetError> = serde_json::from_str(&local_var_content).ok();
+        let local_var_error = ResponseContent {
+            status: local_var_status,
+            content: local_var_content,
+            entity: local_var_entity,
+        };
+        Err(Error::ResponseError(local_var_error))
+    }
+}
+
+/// Get quote for a given input mint, output mint and amount
+pub async fn quote_get(
+    configuration: &configuration::Configuration,
+    request: &crate::models::QuoteRequest,
+) -> Result<SwapRoute, Error<QuoteGetError>> {
+    let local_var_configuration = configuration;
+    let local_var_timeout = request.timeout;
+
+    let local_var_client = &local_var_configuration.client;
+
+    let local_var_uri_str = format!("{}/quote", local_var_configuration.base_path);
+    let mut local_var_req_builder =
+        local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+    local_var_req_builder = local_var_req_builder.query(request);
     if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
         local_var_req_builder =
             local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
     }
+    local_var_req_builder = local_var_configuration.apply_credentials(local_var_req_builder);
 
     let local_var_req = local_var_req_builder.build()?;
-    let local_var_resp = local_var_client.execute(local_var_req).await?;
+    let local_var_resp = execute(local_var_configuration, local_var_req, local_var_timeout).await?;
 
     let local_var_status = local_var_resp.status();
     let local_var_content = local_var_resp.text().await?;
@@ -200,8 +249,10 @@ pub async fn quote_get(
 pub async fn swap_post(
     configuration: &configuration::Configuration,
     body: crate::models::SwapPostRequest,
+    timeout: Option<Duration>,
 ) -> Result<crate::models::SwapPost200Response, Error<SwapPostError>> {
     let local_var_configuration = configuration;
+    let local_var_timeout = timeout;
 
     let local_var_client = &local_var_configuration.client;
 
@@ -213,10 +264,11 @@ pub async fn swap_post(
         local_var_req_builder =
             local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
     }
+    local_var_req_builder = local_var_configuration.apply_credentials(local_var_req_builder);
     local_var_req_builder = local_var_req_builder.json(&body);
 
     let local_var_req = local_var_req_builder.build()?;
-    let local_var_resp = local_var_client.execute(local_var_req).await?;
+    let local_var_resp = execute(local_var_configuration, local_var_req, local_var_timeout).await?;
 
     let local_var_status = local_var_resp.status();
     let local_var_content = local_var_resp.text().await?;
@@ -233,3 +285,45 @@ pub async fn swap_post(
         Err(Error::ResponseError(local_var_error))
     }
 }
+
+/// Get swap instructions for a route
+pub async fn swap_instructions_post(
+    configuration: &configuration::Configuration,
+    body: crate::models::SwapPostRequest,
+    timeout: Option<Duration>,
+) -> Result<crate::models::SwapInstructionsPost200Response, Error<SwapInstructionsPostError>> {
+    let local_var_configuration = configuration;
+    let local_var_timeout = timeout;
+
+    let local_var_client = &local_var_configuration.client;
+
+    let local_var_uri_str = format!("{}/swap-instructions", local_var_configuration.base_path);
+    let mut local_var_req_builder =
+        local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
+
+    if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+        local_var_req_builder =
+            local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+    }
+    local_var_req_builder = local_var_configuration.apply_credentials(local_var_req_builder);
+    local_var_req_builder = local_var_req_builder.json(&body);
+
+    let local_var_req = local_var_req_builder.build()?;
+    let local_var_resp = execute(local_var_configuration, local_var_req, local_var_timeout).await?;
+
+    let local_var_status = local_var_resp.status();
+    let local_var_content = local_var_resp.text().await?;
+
+    if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+        serde_json::from_str(&local_var_content).map_err(Error::from)
+    } else {
+        let local_var_entity: Option<SwapInstructionsPostError> =
+            serde_json::from_str(&local_var_content).ok();
+        let local_var_error = ResponseContent {
+            status: local_var_status,
+            content: local_var_content,
+            entity: local_var_entity,
+        };
+        Err(Error::ResponseError(local_var_error))
+    }
+}
@@ -1,8 +1,10 @@
 pub mod configuration;
 pub mod jup_api;
+pub mod retry;
 
 use std::error;
 use std::fmt;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone)]
 pub struct ResponseContent<T> {
@@ -11,12 +13,81 @@ pub struct ResponseContent<T> {
     pub entity: Option<T>,
 }
 
+/// Execute a request, retrying and rate limiting it according to the configuration
+///
+/// The configured timeout bounds every attempt, unless a `deadline` is given: it then
+/// replaces the configured timeout and bounds the whole call, retries and backoffs included.
+pub(crate) async fn execute<T>(
+    configuration: &configuration::Configuration,
+    mut request: reqwest::Request,
+    deadline: Option<Duration>,
+) -> Result<reqwest::Response, Error<T>> {
+    let policy = &configuration.retry_policy;
+    let deadline = deadline.map(|timeout| (timeout, Instant::now() + timeout));
+    let mut attempt = 1;
+    loop {
+        if let Some(rate_limiter) = &configuration.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+        *request.timeout_mut() = match deadline {
+            Some((timeout, deadline_at)) => {
+                let remaining = deadline_at.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Err(Error::Timeout(timeout));
+                }
+                Some(remaining)
+            }
+            None => configuration.timeout,
+        };
+        let retry_request = match request.try_clone() {
+            Some(retry_request) if attempt < policy.max_attempts => retry_request,
+            // Last attempt or streamed body that cannot be replayed
+            _ => {
+                return configuration
+                    .client
+                    .execute(request)
+                    .await
+                    .map_err(Error::from)
+            }
+        };
+        let delay = match configuration.client.execute(retry_request).await {
+            Ok(response) if policy.is_retryable_status(response.status()) => {
+                match retry::retry_after(&response) {
+                    // Retrying before the server allows it would only be rejected again
+                    Some(delay)
+                        if delay > policy.max_backoff
+                            || deadline.is_some_and(|(_, deadline_at)| {
+                                Instant::now() + delay >= deadline_at
+                            }) =>
+                    {
+                        return Ok(response)
+                    }
+                    Some(delay) => delay,
+                    None => policy.backoff(attempt),
+                }
+            }
+            Err(error) if policy.is_retryable_error(&error) => policy.backoff(attempt),
+            result => return result.map_err(Error::from),
+        };
+        if let Some((timeout, deadline_at)) = deadline {
+            if Instant::now() + delay >= deadline_at {
+                return Err(Error::Timeout(timeout));
+            }
+        }
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug)]
 pub enum Error<T> {
     Reqwest(reqwest::Error),
     Serde(serde_json::Error),
     Io(std::io::Error),
     ResponseError(ResponseContent<T>),
+    /// The call deadline elapsed
+    Timeout(Duration),
 }
 
 impl<T> fmt::Display for Error<T> {
@@ -26,6 +97,7 @@ impl<T> fmt::Display for Error<T> {
             Error::Serde(e) => ("serde", e.to_string()),
             Error::Io(e) => ("IO", e.to_string()),
             Error::ResponseError(e) => ("response", format!("status code {}", e.status)),
+            Error::Timeout(timeout) => ("deadline", format!("elapsed after {:?}", timeout)),
         };
         write!(f, "error in {}: {}", module, e)
     }
@@ -37,7 +109,7 @@ impl<T: fmt::Debug> error::Error for Error<T> {
             Error::Reqwest(e) => e,
             Error::Serde(e) => e,
             Error::Io(e) => e,
-            Error::ResponseError(_) => return None,
+            Error::ResponseError(_) | Error::Timeout(_) => return None,
         })
     }
 }
@@ -0,0 +1,135 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use rand::Rng;
+use reqwest::{header::RETRY_AFTER, Response, StatusCode};
+
+/// Retry policy applied to every request sent to the Jupiter API
+///
+/// Requests failing with a 429, a 5xx or a connection error are retried with an exponential
+/// backoff and full jitter. A `Retry-After` header sent by the server takes precedence over
+/// the computed backoff; when it asks for more than `max_backoff`, or past the call deadline,
+/// the response is returned right away instead of being retried too early.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Backoff before the first retry, doubled for every following retry
+    pub initial_backoff: Duration,
+    /// Upper bound of the computed backoff and longest `Retry-After` delay waited for
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Send every request only once
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_retryable_status(&self, status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
+
+    pub fn is_retryable_error(&self, error: &reqwest::Error) -> bool {
+        error.is_connect() || error.is_timeout()
+    }
+
+    /// Delay to wait before the given retry (1 for the first retry)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponential = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+        let jitter_ms = rand::thread_rng().gen_range(0..=exponential.as_millis() as u64);
+        Duration::from_millis(jitter_ms)
+    }
+}
+
+/// Delay requested by the server through the `Retry-After` header, in seconds or as an HTTP date
+pub(crate) fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => {
+            let date = httpdate::parse_http_date(value).ok()?;
+            Some(
+                date.duration_since(SystemTime::now())
+                    .unwrap_or(Duration::ZERO),
+            )
+        }
+    }
+}
+
+/// Client side token bucket limiting the rate of requests sent to the Jupiter API
+///
+/// Share it between configurations with an `Arc` so that quote, swap and price calls all draw
+/// from the same bucket.
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_second: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Allow `requests_per_second` on average with bursts of up to `burst` requests
+    ///
+    /// Panics if `requests_per_second` is not a finite positive number.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        assert!(
+            requests_per_second.is_finite() && requests_per_second > 0.0,
+            "requests_per_second must be a finite positive number, got {requests_per_second}"
+        );
+        let capacity = f64::from(burst.max(1));
+        RateLimiter {
+            capacity,
+            refill_per_second: requests_per_second,
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until a token is available and consume it
+    pub async fn acquire(&self) {
+        while let Some(wait) = self.try_acquire() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Consume a token if available, otherwise return the time to wait for the next one
+    fn try_acquire(&self) -> Option<Duration> {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        bucket.last_refill = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            let missing = 1.0 - bucket.tokens;
+            Some(Duration::from_secs_f64(missing / self.refill_per_second))
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Synchronous API, enabled with the `blocking` feature
+//!
+//! The client drives the async one on its own runtime, like `reqwest::blocking`. It must not
+//! be called from within an async runtime.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::MAX_MULTIPLE_ACCOUNTS;
+use solana_program::hash::Hash;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use solana_sdk::transaction::VersionedTransaction;
+use tokio::runtime::Runtime;
+
+use crate::apis::configuration::Configuration;
+use crate::errors::Result;
+use crate::models::{
+    BulkPrices, PriceId, PriceV2, QuoteRequest, RouteMap, SwapOptions, SwapPrice, SwapRoute,
+};
+use crate::reexports::Decimal;
+use crate::tx_composer::{FittedSwap, TxComposer};
+use crate::utils::{
+    decompile_transaction_instructions, AsyncAccountFetcher, BasicAddressLoader,
+    DecompiledVersionedTx, SwapInstructionSet,
+};
+
+/// Synchronous counterpart of `AsyncAccountFetcher`
+pub trait AccountFetcher {
+    fn fech_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>>;
+    fn get_latest_blockhash(&self) -> Result<Hash>;
+}
+
+impl AccountFetcher for RpcClient {
+    fn fech_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        let mut accounts = Vec::with_capacity(pubkeys.len());
+        for batch in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            accounts.extend(self.get_multiple_accounts(batch)?);
+        }
+        Ok(accounts)
+    }
+
+    fn get_latest_blockhash(&self) -> Result<Hash> {
+        self.get_latest_blockhash().map_err(Into::into)
+    }
+}
+
+/// Lets the async client use a sync fetcher
+///
+/// Calls go through `block_in_place`, which `RpcClient` also uses internally whenever it runs
+/// inside a runtime: the client runtime has to be multi threaded for it not to panic.
+struct AsyncAdapter<'a, F>(&'a F);
+
+#[async_trait]
+impl<F: AccountFetcher + Sync> AsyncAccountFetcher for AsyncAdapter<'_, F> {
+    async fn fech_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        tokio::task::block_in_place(|| self.0.fech_accounts(pubkeys))
+    }
+
+    async fn get_latest_blockhash(&self) -> Result<Hash> {
+        tokio::task::block_in_place(|| self.0.get_latest_blockhash())
+    }
+}
+
+/// Decompiles a transaction, fetching the lookup tables it references with the provided fetcher
+pub fn decompile_transaction_instructions_with_fetcher(
+    tx: VersionedTransaction,
+    accounts_fetcher: &impl AccountFetcher,
+) -> Result<DecompiledVersionedTx> {
+    let lookup_tables_pk = tx.message.address_table_lookups().map(|tables| {
+        tables
+            .iter()
+            .map(|table| table.account_key)
+            .collect::<Vec<_>>()
+    });
+    let address_loader = match lookup_tables_pk {
+        Some(ref tables) => Some(BasicAddressLoader::from_accounts_and_accounts_fetcher(
+            tables,
+            |pubkeys| accounts_fetcher.fech_accounts(pubkeys),
+        )?),
+        None => None,
+    };
+
+    let instructions = decompile_transaction_instructions(tx, address_loader.as_ref())?;
+    Ok(DecompiledVersionedTx {
+        lookup_tables: address_loader.map(|l| l.to_address_lookup_table_accounts()),
+        instructions,
+    })
+}
+
+/// Blocking Jupiter API client, see `crate::JupiterClient` for the operations
+///
+/// Clones share the same runtime and HTTP client.
+#[derive(Debug, Clone)]
+pub struct JupiterClient {
+    inner: crate::JupiterClient,
+    runtime: Arc<Runtime>,
+}
+
+impl JupiterClient {
+    /// Create a client with default configuration targeting `base_url`
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_configuration(Configuration::new(base_url))
+    }
+
+    /// Create a client from a fully customized `Configuration`
+    pub fn with_configuration(configuration: Configuration) -> Self {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            // Same failure mode as `reqwest::blocking::Client::new`
+            .expect("tokio runtime cannot be initialized");
+        Self {
+            inner: crate::JupiterClient::with_configuration(configuration),
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    pub fn configuration(&self) -> &Configuration {
+        self.inner.configuration()
+    }
+
+    /// The async client sharing this client configuration and caches
+    pub fn as_async(&self) -> &crate::JupiterClient {
+        &self.inner
+    }
+
+    pub fn get_mint_decimals(
+        &self,
+        mints: &[Pubkey],
+        accounts_fetcher: &(impl AccountFetcher + Sync),
+    ) -> Result<Vec<u8>> {
+        self.runtime.block_on(
+            self.inner
+                .get_mint_decimals(mints, &AsyncAdapter(accounts_fetcher)),
+        )
+    }
+
+    pub fn get_prices(
+        &self,
+        input_mints: &[Pubkey],
+        output_mint: &Pubkey,
+        amount: Decimal,
+    ) -> Result<HashMap<String, SwapPrice>> {
+        self.runtime
+            .block_on(self.inner.get_prices(input_mints, output_mint, amount))
+    }
+
+    pub fn get_prices_by_id(
+        &self,
+        ids: &[PriceId],
+        vs_token: &PriceId,
+        amount: Decimal,
+    ) -> Result<HashMap<String, SwapPrice>> {
+        self.runtime
+            .block_on(self.inner.get_prices_by_id(ids, vs_token, amount))
+    }
+
+    pub fn get_prices_bulk(
+        &self,
+        mints: &[Pubkey],
+        vs_token: &Pubkey,
+        amount: Decimal,
+    ) -> Result<BulkPrices> {
+        self.runtime
+            .block_on(self.inner.get_prices_bulk(mints, vs_token, amount))
+    }
+
+    pub fn get_price_details(
+        &self,
+        ids: &[Pubkey],
+        vs_token: Option<&Pubkey>,
+        show_extra_info: bool,
+    ) -> Result<HashMap<String, PriceV2>> {
+        self.runtime
+            .block_on(self.inner.get_price_details(ids, vs_token, show_extra_info))
+    }
+
+    pub fn get_quote(&self, request: &QuoteRequest) -> Result<SwapRoute> {
+        self.runtime.block_on(self.inner.get_quote(request))
+    }
+
+    pub fn get_quote_ui(
+        &self,
+        input_mint: &Pubkey,
+        output_mint: &Pubkey,
+        ui_amount: Decimal,
+        accounts_fetcher: &(impl AccountFetcher + Sync),
+    ) -> Result<SwapRoute> {
+        self.runtime.block_on(self.inner.get_quote_ui(
+            input_mint,
+            output_mint,
+            ui_amount,
+            &AsyncAdapter(accounts_fetcher),
+        ))
+    }
+
+    pub fn get_swap_transactions(
+        &self,
+        route: impl Into<Box<SwapRoute>>,
+        user_public_key: Pubkey,
+        options: &SwapOptions,
+    ) -> Result<VersionedTransaction> {
+        self.runtime.block_on(
+            self.inner
+                .get_swap_transactions(route, user_public_key, options),
+        )
+    }
+
+    pub fn get_route_map(&self, only_direct_routes: bool) -> Result<RouteMap> {
+        self.runtime
+            .block_on(self.inner.get_route_map(only_direct_routes))
+    }
+
+    pub fn get_swap_instructions(
+        &self,
+        route: impl Into<Box<SwapRoute>>,
+        user_public_key: Pubkey,
+        options: &SwapOptions,
+        accounts_fetcher: &(impl AccountFetcher + Sync),
+    ) -> Result<DecompiledVersionedTx> {
+        self.runtime.block_on(self.inner.get_swap_instructions(
+            route,
+            user_public_key,
+            options,
+            &AsyncAdapter(accounts_fetcher),
+        ))
+    }
+
+    pub fn get_swap_instruction_set(
+        &self,
+        route: impl Into<Box<SwapRoute>>,
+        user_public_key: Pubkey,
+        options: &SwapOptions,
+        accounts_fetcher: &(impl AccountFetcher + Sync),
+    ) -> Result<SwapInstructionSet> {
+        self.runtime.block_on(self.inner.get_swap_instruction_set(
+            route,
+            user_public_key,
+            options,
+            &AsyncAdapter(accounts_fetcher),
+        ))
+    }
+
+    pub fn get_best_swap_instructions(
+        &self,
+        request: &QuoteRequest,
+        price_impact_limit: Option<Decimal>,
+        user_public_key: Pubkey,
+        options: &SwapOptions,
+        accounts_fetcher: &(impl AccountFetcher + Sync),
+    ) -> Result<DecompiledVersionedTx> {
+        self.runtime.block_on(self.inner.get_best_swap_instructions(
+            request,
+            price_impact_limit,
+            user_public_key,
+            options,
+            &AsyncAdapter(accounts_fetcher),
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_fitting_swap_transaction(
+        &self,
+        request: &QuoteRequest,
+        price_impact_limit: Option<Decimal>,
+        user_public_key: Pubkey,
+        options: &SwapOptions,
+        composer: &TxComposer,
+        account_lock_limit: usize,
+        accounts_fetcher: &(impl AccountFetcher + Sync),
+    ) -> Result<FittedSwap> {
+        self.runtime
+            .block_on(self.inner.get_fitting_swap_transaction(
+                request,
+                price_impact_limit,
+                user_public_key,
+                options,
+                composer,
+                account_lock_limit,
+                &AsyncAdapter(accounts_fetcher),
+            ))
+    }
+}
@@ -0,0 +1,407 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::{stream, StreamExt, TryStreamExt};
+use itertools::Itertools;
+use solana_sdk::transaction::VersionedTransaction;
+
+use crate::apis::configuration::{Configuration, PriceApiVersion};
+use crate::apis::jup_api;
+use crate::errors::{Error, Result};
+use crate::mint::{ui_amount_to_amount, MintDecimalsCache};
+use crate::models::{
+    swap_price::SwapPrice, BulkPrices, PriceId, PriceV2, QuoteRequest, RouteMap, SwapOptions,
+    SwapPostRequest, SwapRoute,
+};
+use crate::reexports::{Decimal, Pubkey};
+use crate::tx_composer::{FittedSwap, TxComposer};
+use crate::utils::{
+    decompile_transaction_instructions_with_async_fetcher_at_slot,
+    swap_instruction_set_with_async_fetcher_at_slot, AsyncAccountFetcher, DecompiledVersionedTx,
+    SwapInstructionSet,
+};
+
+/// `max_accounts` of the first quote of `get_fitting_swap_transaction` when not set
+pub const DEFAULT_MAX_ACCOUNTS: u8 = 64;
+
+/// Smallest `max_accounts` tried by `get_fitting_swap_transaction` before direct routes
+pub const MIN_MAX_ACCOUNTS: u8 = 8;
+
+/// Minimum decrease of `max_accounts` between two quotes of `get_fitting_swap_transaction`
+const MAX_ACCOUNTS_STEP: u8 = 4;
+
+/// Jupiter API client owning its own `Configuration`
+///
+/// Several clients pointing to different hosts can live in the same process, the underlying
+/// `reqwest::Client` is shared between all the calls made by a given client.
+#[derive(Debug, Clone, Default)]
+pub struct JupiterClient {
+    configuration: Configuration,
+    /// Shared between all the clones of this client
+    mint_decimals: Arc<MintDecimalsCache>,
+}
+
+impl JupiterClient {
+    /// Create a client with default configuration targeting `base_url`
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_configuration(Configuration::new(base_url))
+    }
+
+    /// Create a client from a fully customized `Configuration`
+    pub fn with_configuration(configuration: Configuration) -> Self {
+        Self {
+            configuration,
+            mint_decimals: Arc::default(),
+        }
+    }
+
+    pub fn configuration(&self) -> &Configuration {
+        &self.configuration
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.configuration.base_path
+    }
+
+    pub fn price_base_url(&self) -> &str {
+        self.configuration.price_base_path()
+    }
+
+    pub fn mint_decimals(&self) -> &MintDecimalsCache {
+        &self.mint_decimals
+    }
+
+    /// Get the decimals of SPL Token or Token-2022 mints, cached for the client lifetime
+    pub async fn get_mint_decimals(
+        &self,
+        mints: &[Pubkey],
+        accounts_fetcher: &impl AsyncAccountFetcher,
+    ) -> Result<Vec<u8>> {
+        self.mint_decimals
+            .get_or_fetch(mints, accounts_fetcher)
+            .await
+    }
+
+    /// Get simple price for a given input mint, output mint and amount
+    ///
+    /// Uses the configured `price_api_version`, the amount and the symbols are not supported by
+    /// the v2 API and are ignored.
+    pub async fn get_prices(
+        &self,
+        input_mints: &[Pubkey],
+        output_mint: &Pubkey,
+        amount: Decimal,
+    ) -> Result<HashMap<String, SwapPrice>> {
+        let ids = input_mints
+            .iter()
+            .copied()
+            .map(PriceId::from)
+            .collect::<Vec<_>>();
+        self.get_prices_by_id(&ids, &PriceId::from(*output_mint), amount)
+            .await
+    }
+
+    /// Get simple price of tokens looked up by mint or by symbol, keyed by the requested id
+    ///
+    /// A symbol matching several tokens fails with `Error::AmbiguousPriceSymbol`, symbols are
+    /// rejected by the v2 API.
+    pub async fn get_prices_by_id(
+        &self,
+        ids: &[PriceId],
+        vs_token: &PriceId,
+        amount: Decimal,
+    ) -> Result<HashMap<String, SwapPrice>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let version = self.configuration.price_api_version;
+        if version != PriceApiVersion::V2 {
+            let raw_price = jup_api::prices_get(&self.configuration, ids, vs_token, amount)
+                .await
+                .map_err(Error::from_price_error)?;
+            return Ok(raw_price.data);
+        }
+        let (mints, vs_token) = ids
+            .iter()
+            .map(|id| id.mint().copied())
+            .collect::<Option<Vec<_>>>()
+            .zip(vs_token.mint())
+            .ok_or(Error::PriceSymbolUnsupported(version))?;
+        let prices = self
+            .get_price_details(&mints, Some(vs_token), false)
+            .await?;
+        Ok(prices
+            .into_iter()
+            .map(|(id, price)| {
+                let price = SwapPrice {
+                    id: price.id,
+                    mint_symbol: None,
+                    vs_token: *vs_token,
+                    vs_token_symbol: None,
+                    price: price.price,
+                };
+                (id, price)
+            })
+            .collect())
+    }
+
+    /// Get the prices of any number of mints, split in chunks fetched concurrently
+    ///
+    /// Chunk size and parallelism are set by the configuration, the first failing chunk fails
+    /// the whole call.
+    pub async fn get_prices_bulk(
+        &self,
+        mints: &[Pubkey],
+        vs_token: &Pubkey,
+        amount: Decimal,
+    ) -> Result<BulkPrices> {
+        let mints = mints.iter().copied().unique().collect::<Vec<_>>();
+        let chunks = stream::iter(mints.chunks(self.configuration.price_chunk_size.max(1)))
+            .map(|chunk| self.get_prices(chunk, vs_token, amount))
+            .buffer_unordered(self.configuration.price_concurrency.max(1))
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let prices = chunks
+            .into_iter()
+            .flat_map(HashMap::into_values)
+            .map(|price| (price.id, price))
+            .collect::<HashMap<_, _>>();
+        let missing = mints
+            .into_iter()
+            .filter(|mint| !prices.contains_key(mint))
+            .collect();
+        Ok(BulkPrices { prices, missing })
+    }
+
+    /// Get the prices from the v2 API whatever the configured `price_api_version`, with the
+    /// last swapped and quoted prices, confidence level and depth if `show_extra_info`
+    ///
+    /// Ids without a price are left out, USDC is the default vs token.
+    pub async fn get_price_details(
+        &self,
+        ids: &[Pubkey],
+        vs_token: Option<&Pubkey>,
+        show_extra_info: bool,
+    ) -> Result<HashMap<String, PriceV2>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let raw_price =
+            jup_api::prices_v2_get(&self.configuration, ids, vs_token, show_extra_info).await?;
+        Ok(raw_price
+            .data
+            .into_iter()
+            .filter_map(|(id, price)| Some((id, price?)))
+            .collect())
+    }
+
+    /// Get quotes for a given input mint, output mint and amount
+    pub async fn get_quote(&self, request: &QuoteRequest) -> Result<SwapRoute> {
+        let raw_quote = jup_api::quote_get(&self.configuration, request).await?;
+        Ok(raw_quote)
+    }
+
+    /// Get quotes for an input amount in UI units, the mint decimals are resolved with the
+    /// provided fetcher
+    ///
+    /// Use `get_quote` with `mint::ui_amount_to_amount` to set other quote parameters.
+    pub async fn get_quote_ui(
+        &self,
+        input_mint: &Pubkey,
+        output_mint: &Pubkey,
+        ui_amount: Decimal,
+        accounts_fetcher: &impl AsyncAccountFetcher,
+    ) -> Result<SwapRoute> {
+        let decimals = self
+            .get_mint_decimals(&[*input_mint, *output_mint], accounts_fetcher)
+            .await?;
+        let amount = ui_amount_to_amount(ui_amount, decimals[0])?;
+        self.get_quote(&QuoteRequest::new(*input_mint, *output_mint, amount))
+            .await
+    }
+
+    /// Get swap serialized transactions for a quote
+    pub async fn get_swap_transactions(
+        &self,
+        route: impl Into<Box<SwapRoute>>,
+        user_public_key: Pubkey,
+        options: &SwapOptions,
+    ) -> Result<VersionedTransaction> {
+        let request = SwapPostRequest::new(route, user_public_key, options);
+        let raw_swap = jup_api::swap_post(&self.configuration, request, options.timeout).await?;
+
+        let decode = |base64_transaction: String| -> Result<VersionedTransaction> {
+            bincode::deserialize(&base64::decode(base64_transaction)?).map_err(|err| err.into())
+        };
+
+        decode(raw_swap.swap_transaction)
+    }
+
+    /// Returns a hash map, input mint as key and an array of valid output mint as values
+    pub async fn get_route_map(&self, only_direct_routes: bool) -> Result<RouteMap> {
+        let raw_route_map =
+            jup_api::indexed_route_map_get(&self.configuration, only_direct_routes).await?;
+        Ok(raw_route_map)
+    }
+
+    /// Get decompiled instructions but requires a fetcher to retrieve the lookup tables
+    ///
+    /// The lookup tables are fetched at the quote context slot or later, tables deactivated or
+    /// being deactivated are rejected.
+    ///
+    /// Note: it is very recommended to enforce single swap tx
+    pub async fn get_swap_instructions(
+        &self,
+        route: impl Into<Box<SwapRoute>>,
+        user_public_key: Pubkey,
+        options: &SwapOptions,
+        accounts_fetcher: &impl AsyncAccountFetcher,
+    ) -> Result<DecompiledVersionedTx> {
+        let route = route.into();
+        let context_slot = route.context_slot;
+        let transactions = self
+            .get_swap_transactions(route, user_public_key, options)
+            .await?;
+        decompile_transaction_instructions_with_async_fetcher_at_slot(
+            transactions,
+            context_slot,
+            accounts_fetcher,
+        )
+        .await
+    }
+
+    /// Get the swap instructions directly from the swap-instructions endpoint, only the lookup
+    /// tables are fetched with the provided fetcher
+    ///
+    /// Prefer it over `get_swap_instructions` which has to decompile the swap transaction.
+    /// Lookup tables deactivated or being deactivated are rejected.
+    pub async fn get_swap_instruction_set(
+        &self,
+        route: impl Into<Box<SwapRoute>>,
+        user_public_key: Pubkey,
+        options: &SwapOptions,
+        accounts_fetcher: &impl AsyncAccountFetcher,
+    ) -> Result<SwapInstructionSet> {
+        let request = SwapPostRequest::new(route, user_public_key, options);
+        let context_slot = request.route.context_slot;
+        let raw_swap_instructions =
+            jup_api::swap_instructions_post(&self.configuration, request, options.timeout).await?;
+        swap_instruction_set_with_async_fetcher_at_slot(
+            raw_swap_instructions,
+            context_slot,
+            accounts_fetcher,
+        )
+        .await
+    }
+
+    /// Get a swap transaction wrapped between the instructions of `composer`, re-quoting with
+    /// a smaller `max_accounts` until it fits in a packet and `account_lock_limit`
+    ///
+    /// The `max_accounts` of `request` is the starting point, then direct routes are tried
+    /// when the smallest routes do not fit or no route is found.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_fitting_swap_transaction(
+        &self,
+        request: &QuoteRequest,
+        price_impact_limit: Option<Decimal>,
+        user_public_key: Pubkey,
+        options: &SwapOptions,
+        composer: &TxComposer,
+        account_lock_limit: usize,
+        accounts_fetcher: &impl AsyncAccountFetcher,
+    ) -> Result<FittedSwap> {
+        let mut request = request.clone();
+        let mut max_accounts = request.max_accounts.unwrap_or(DEFAULT_MAX_ACCOUNTS);
+        let mut last_error = None;
+        // Smallest route measured so far, reported if no smaller route is found
+        let mut last_estimate = None;
+        loop {
+            request.max_accounts = Some(max_accounts);
+            let estimate = match self.get_quote(&request).await {
+                Ok(route) => {
+                    if let Some(price_impact_limit) = price_impact_limit {
+                        if route.price_impact_pct > price_impact_limit {
+                            return Err(Error::PriceImpactTooHigh(route.price_impact_pct));
+                        }
+                    }
+                    let instruction_set = self
+                        .get_swap_instruction_set(
+                            route.clone(),
+                            user_public_key,
+                            options,
+                            accounts_fetcher,
+                        )
+                        .await?;
+                    let composer = composer.clone().swap(instruction_set.clone());
+                    let estimate = composer.estimate(account_lock_limit);
+                    if estimate.fits() {
+                        let recent_blockhash = accounts_fetcher.get_latest_blockhash().await?;
+                        match composer.build_unsigned(recent_blockhash) {
+                            Ok(transaction) => {
+                                return Ok(FittedSwap {
+                                    route,
+                                    instruction_set,
+                                    composer,
+                                    estimate,
+                                    transaction,
+                                })
+                            }
+                            // The estimate missed, a smaller route can still fit
+                            Err(err @ Error::TransactionTooLarge { .. }) => {
+                                last_error = Some(Box::new(err))
+                            }
+                            Err(err) => return Err(err),
+                        }
+                    }
+                    Some(estimate)
+                }
+                // Fewer accounts can leave no route at all
+                Err(Error::NoValidRoute) => None,
+                Err(err) => return Err(err),
+            };
+            if estimate.is_some() {
+                last_estimate = estimate.clone();
+            }
+
+            let next = estimate
+                .as_ref()
+                .map_or(0, |estimate| estimate.suggested_max_accounts)
+                .min(max_accounts.saturating_sub(MAX_ACCOUNTS_STEP));
+            if next >= MIN_MAX_ACCOUNTS {
+                max_accounts = next;
+            } else if request.only_direct_routes != Some(true) {
+                request.only_direct_routes = Some(true);
+            } else {
+                return Err(match last_estimate {
+                    Some(estimate) => Error::SwapDoesNotFit {
+                        size: estimate.size,
+                        account_count: estimate.account_count,
+                        source: last_error,
+                    },
+                    None => Error::NoValidRoute,
+                });
+            }
+        }
+    }
+
+    /// Get the swap instructions for the best route matching parameters
+    pub async fn get_best_swap_instructions(
+        &self,
+        request: &QuoteRequest,
+        price_impact_limit: Option<Decimal>,
+        user_public_key: Pubkey,
+        options: &SwapOptions,
+        accounts_fetcher: &impl AsyncAccountFetcher,
+    ) -> Result<DecompiledVersionedTx> {
+        let best_route = self.get_quote(request).await?;
+
+        if let Some(price_impact_limit) = price_impact_limit {
+            if best_route.price_impact_pct > price_impact_limit {
+                return Err(Error::PriceImpactTooHigh(best_route.price_impact_pct));
+            }
+        }
+        self.get_swap_instructions(best_route, user_public_key, options, accounts_fetcher)
+            .await
+    }
+}
@@ -1,10 +1,21 @@
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
 use solana_client::client_error::ClientError;
 use solana_program::{
-    instruction::InstructionError, message::SanitizeMessageError, sanitize::SanitizeError,
+    instruction::InstructionError,
+    message::{CompileError, SanitizeMessageError},
+    sanitize::SanitizeError,
 };
-use solana_sdk::pubkey::ParsePubkeyError;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::pubkey::{ParsePubkeyError, Pubkey};
+use solana_sdk::signer::SignerError;
+use std::fmt;
 use thiserror::Error;
 
+use crate::apis::configuration::PriceApiVersion;
+use crate::apis::jup_api::PriceGetError;
+use crate::models::{ApiErrorResponse, JupiterErrorCode, PriceGet409Response};
+
 /// A `Result` alias where the `Err` case is `jupv4-openapi::Error`.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -26,13 +37,31 @@ pub enum Error {
     Bincode(#[from] bincode::Error),
 
     #[error("Jupiter API: {0}")]
-    JupiterApi(String),
+    JupiterApi(JupiterApiError),
+
+    #[error("Jupiter API rate limit exceeded: {0}")]
+    RateLimited(JupiterApiError),
+
+    #[error("Jupiter API server error: {0}")]
+    JupiterServer(JupiterApiError),
+
+    #[error("Jupiter API request timed out")]
+    Timeout,
 
     #[error("No route found for the requested swap")]
     NoValidRoute,
 
+    #[error("{message}, candidates: {addresses:?}")]
+    AmbiguousPriceSymbol {
+        message: String,
+        addresses: Vec<Pubkey>,
+    },
+
+    #[error("The {0:?} price API only accepts mint addresses")]
+    PriceSymbolUnsupported(PriceApiVersion),
+
     #[error("Price impact too high")]
-    PriceImpactTooHigh(f32),
+    PriceImpactTooHigh(Decimal),
 
     #[error("serde_json: {0}")]
     SerdeJson(#[from] serde_json::Error),
@@ -43,35 +72,171 @@ pub enum Error {
     #[error("Error while decompiling provided transaction: {0:?}")]
     SanitizeError(#[from] SanitizeError),
 
+    #[error(
+        "Error while decompiling provided transaction: instruction {instruction_index} references missing account {account_index}"
+    )]
+    DecompileMissingAccount {
+        instruction_index: usize,
+        account_index: u8,
+    },
+
     #[error("Lookup table deserialization failed: {0:?}")]
     LookupTableDeserialize(#[from] InstructionError),
 
     #[error("Lookup table account was not found onchain")]
     LookupTableAccountNotFound,
 
+    #[error("Lookup table {0} is deactivated or being deactivated")]
+    LookupTableDeactivated(Pubkey),
+
+    #[error("Account fetcher returned {returned} accounts for {requested} keys")]
+    AccountCountMismatch { requested: usize, returned: usize },
+
+    #[error("Mint account {0} was not found onchain")]
+    MintAccountNotFound(Pubkey),
+
+    #[error("Account {0} is not an initialized SPL Token or Token-2022 mint")]
+    InvalidMintAccount(Pubkey),
+
+    #[error("Amount {0} cannot be represented with the mint decimals")]
+    InvalidUiAmount(Decimal),
+
+    #[error(
+        "Swap does not fit in a transaction: {size} bytes and {account_count} accounts with the smallest route"
+    )]
+    SwapDoesNotFit {
+        size: usize,
+        account_count: usize,
+        /// Last error building a transaction estimated to fit
+        #[source]
+        source: Option<Box<Error>>,
+    },
+
     #[error("Solana client error: {0:?}")]
-    SolanaRpcError(#[from] ClientError),
+    SolanaRpcError(Box<ClientError>),
+
+    #[error("Solana message compile error: {0}")]
+    SolanaCompile(#[from] CompileError),
+
+    #[error("Solana transaction signing error: {0}")]
+    SolanaSigner(#[from] SignerError),
 
-    #[error("Solana transaction compile error")]
-    SolanaCompileError,
+    #[error("Transaction is {size} bytes, {over_limit} over the {PACKET_DATA_SIZE} bytes limit")]
+    TransactionTooLarge { size: usize, over_limit: usize },
 
     #[error("Response type conversion error")]
     ResponseTypeConversionError,
 
     #[error("Base URL already set")]
     BaseUrlAlreadySet,
+
+    #[error("Price base URL already set")]
+    PriceBaseUrlAlreadySet,
+}
+
+/// Details of an error status returned by the Jupiter API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JupiterApiError {
+    pub status: StatusCode,
+    pub code: Option<JupiterErrorCode>,
+    pub message: String,
+}
+
+impl JupiterApiError {
+    pub fn from_response(status: StatusCode, content: &str) -> Self {
+        let body: Option<ApiErrorResponse> = serde_json::from_str(content).ok();
+        let (code, message) = match body {
+            Some(ApiErrorResponse {
+                error: Some(error),
+                error_code,
+            }) => (error_code, error),
+            Some(ApiErrorResponse { error_code, .. }) => (error_code, content.to_owned()),
+            None => (None, content.to_owned()),
+        };
+        JupiterApiError {
+            status,
+            code,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for JupiterApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.code {
+            Some(code) => write!(f, "status {} ({}): {}", self.status, code, self.message),
+            None => write!(f, "status {}: {}", self.status, self.message),
+        }
+    }
+}
+
+impl From<JupiterApiError> for Error {
+    fn from(api_error: JupiterApiError) -> Self {
+        if api_error
+            .code
+            .as_ref()
+            .is_some_and(JupiterErrorCode::is_no_route)
+        {
+            Self::NoValidRoute
+        } else if api_error.status == StatusCode::TOO_MANY_REQUESTS {
+            Self::RateLimited(api_error)
+        } else if api_error.status.is_server_error() {
+            Self::JupiterServer(api_error)
+        } else {
+            Self::JupiterApi(api_error)
+        }
+    }
+}
+
+impl Error {
+    /// Same as the `From` conversion, but a 409 lists the addresses of the duplicated symbol
+    pub(crate) fn from_price_error(api_error: crate::apis::Error<PriceGetError>) -> Self {
+        let conflict = match &api_error {
+            crate::apis::Error::ResponseError(e) if e.status == StatusCode::CONFLICT => {
+                serde_json::from_str::<PriceGet409Response>(&e.content)
+                    .ok()
+                    .and_then(|response| response.data)
+            }
+            _ => None,
+        };
+        let Some(conflict) = conflict else {
+            return api_error.into();
+        };
+        let addresses = conflict
+            .addresses
+            .unwrap_or_default()
+            .iter()
+            .map(|address| address.parse())
+            .collect::<std::result::Result<Vec<_>, _>>();
+        match addresses {
+            Ok(addresses) => Self::AmbiguousPriceSymbol {
+                message: conflict
+                    .error
+                    .unwrap_or_else(|| "Duplicated symbol".to_owned()),
+                addresses,
+            },
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl From<ClientError> for Error {
+    fn from(client_error: ClientError) -> Self {
+        Self::SolanaRpcError(Box::new(client_error))
+    }
 }
 
-impl<T> From<crate::apis::Error<T>> for Error
-where
-    T: core::fmt::Debug,
-{
+impl<T> From<crate::apis::Error<T>> for Error {
     fn from(api_error: crate::apis::Error<T>) -> Self {
         match api_error {
+            crate::apis::Error::Reqwest(e) if e.is_timeout() => Self::Timeout,
             crate::apis::Error::Reqwest(e) => Self::Reqwest(e),
             crate::apis::Error::Serde(e) => Self::SerdeJson(e),
             crate::apis::Error::Io(e) => Self::Io(e),
-            crate::apis::Error::ResponseError(e) => Self::JupiterApi(format!("{:?}", e)),
+            crate::apis::Error::ResponseError(e) => {
+                JupiterApiError::from_response(e.status, &e.content).into()
+            }
+            crate::apis::Error::Timeout(_) => Self::Timeout,
         }
     }
 }
@@ -6,33 +6,52 @@ extern crate serde;
 extern crate serde_json;
 extern crate url;
 
+pub mod account_fetcher;
 mod apis;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod client;
 pub mod errors;
-mod models;
+pub mod mint;
+pub mod models;
+pub mod tx_composer;
+pub mod tx_size;
 pub mod utils;
 use std::collections::HashMap;
 
-pub use apis::configuration::DEFAULT_BASE_URL;
+pub use account_fetcher::CachingAccountFetcher;
+pub use apis::configuration::{
+    ApiKey, BasicAuth, Configuration, PriceApiVersion, API_KEY_HEADER, DEFAULT_BASE_URL,
+    DEFAULT_CONNECT_TIMEOUT, DEFAULT_PRICE_CHUNK_SIZE, DEFAULT_PRICE_CONCURRENCY, DEFAULT_TIMEOUT,
+};
+pub use apis::retry::{RateLimiter, RetryPolicy};
+pub use client::{JupiterClient, DEFAULT_MAX_ACCOUNTS, MIN_MAX_ACCOUNTS};
 use solana_sdk::transaction::VersionedTransaction;
-use utils::decompile_transaction_instructions_with_async_fetcher;
-
-use crate::apis::{configuration::Configuration, jup_api};
-use crate::models::SwapPostRequest;
+pub use tx_composer::{FittedSwap, TxComposer};
+pub use tx_size::TxSizeEstimate;
 
 use reexports::*;
 
 pub mod reexports {
+    pub use rust_decimal::Decimal;
     pub use solana_sdk::pubkey::Pubkey;
 }
 
-pub use errors::{Error, Result};
-pub use models::{swap_price::SwapPrice, swap_route::SwapMode, RouteMap, SwapRoute};
+pub use errors::{Error, JupiterApiError, Result};
+pub use models::{
+    swap_price::SwapPrice,
+    swap_route::{FeeBreakdown, PlatformFee, SwapMode},
+    BulkPrices, JupiterErrorCode, PriceId, PriceV2, QuoteRequest, RouteMap, SwapOptions, SwapRoute,
+};
 pub use utils::AsyncAccountFetcher;
 pub use utils::DecompiledVersionedTx;
+pub use utils::SwapInstructionSet;
 
 use std::sync::OnceLock;
 
 static BASE_URL: OnceLock<String> = OnceLock::new();
+static PRICE_BASE_URL: OnceLock<String> = OnceLock::new();
+static DEFAULT_CLIENT: OnceLock<JupiterClient> = OnceLock::new();
 
 pub fn set_base_url(url: String) -> Result<()> {
     BASE_URL.set(url).map_err(|_| Error::BaseUrlAlreadySet)
@@ -42,74 +61,101 @@ pub fn get_base_url() -> &'static str {
     BASE_URL.get_or_init(|| DEFAULT_BASE_URL.to_string())
 }
 
+/// Set the host used for price requests, defaults to the base URL
+pub fn set_price_base_url(url: String) -> Result<()> {
+    PRICE_BASE_URL
+        .set(url)
+        .map_err(|_| Error::PriceBaseUrlAlreadySet)
+}
+
+pub fn get_price_base_url() -> &'static str {
+    PRICE_BASE_URL.get_or_init(|| get_base_url().to_string())
+}
+
+fn default_client() -> &'static JupiterClient {
+    DEFAULT_CLIENT.get_or_init(|| {
+        JupiterClient::with_configuration(
+            Configuration::new(get_base_url()).with_price_base_path(get_price_base_url()),
+        )
+    })
+}
+
 /// Get simple price for a given input mint, output mint and amount
 pub async fn get_prices(
     input_mints: &[Pubkey],
     output_mint: &Pubkey,
-    amount: f32,
+    amount: Decimal,
 ) -> Result<HashMap<String, SwapPrice>> {
-    let raw_price = jup_api::prices_get(
-        &Configuration::new(get_base_url()),
-        input_mints,
-        output_mint,
-        amount,
-    )
-    .await?;
-    Ok(raw_price.data)
+    default_client()
+        .get_prices(input_mints, output_mint, amount)
+        .await
+}
+
+/// Get simple price of tokens looked up by mint or by symbol, keyed by the requested id
+pub async fn get_prices_by_id(
+    ids: &[PriceId],
+    vs_token: &PriceId,
+    amount: Decimal,
+) -> Result<HashMap<String, SwapPrice>> {
+    default_client()
+        .get_prices_by_id(ids, vs_token, amount)
+        .await
+}
+
+/// Get the prices of any number of mints, split in chunks fetched concurrently
+pub async fn get_prices_bulk(
+    mints: &[Pubkey],
+    vs_token: &Pubkey,
+    amount: Decimal,
+) -> Result<BulkPrices> {
+    default_client()
+        .get_prices_bulk(mints, vs_token, amount)
+        .await
+}
+
+/// Get the prices from the v2 API, with extra info if `show_extra_info`
+pub async fn get_price_details(
+    ids: &[Pubkey],
+    vs_token: Option<&Pubkey>,
+    show_extra_info: bool,
+) -> Result<HashMap<String, PriceV2>> {
+    default_client()
+        .get_price_details(ids, vs_token, show_extra_info)
+        .await
 }
 
-#[allow(clippy::too_many_arguments)]
 /// Get quotes for a given input mint, output mint and amount
-pub async fn get_quote(
+pub async fn get_quote(request: &QuoteRequest) -> Result<SwapRoute> {
+    default_client().get_quote(request).await
+}
+
+/// Get quotes for an input amount in UI units, the mint decimals are resolved with the
+/// provided fetcher
+pub async fn get_quote_ui(
     input_mint: &Pubkey,
     output_mint: &Pubkey,
-    amount: u64,
-    only_direct_routes: bool,
-    slippage_bps: Option<u16>,
-    max_accounts: Option<u8>,
+    ui_amount: Decimal,
+    accounts_fetcher: &impl AsyncAccountFetcher,
 ) -> Result<SwapRoute> {
-    let raw_quote = jup_api::quote_get(
-        &Configuration::new(get_base_url()),
-        input_mint,
-        output_mint,
-        amount,
-        slippage_bps,
-        only_direct_routes,
-        false,
-        max_accounts,
-    )
-    .await?;
-    Ok(raw_quote)
+    default_client()
+        .get_quote_ui(input_mint, output_mint, ui_amount, accounts_fetcher)
+        .await
 }
 
 /// Get swap serialized transactions for a quote
 pub async fn get_swap_transactions(
     route: impl Into<Box<SwapRoute>>,
     user_public_key: Pubkey,
+    options: &SwapOptions,
 ) -> Result<VersionedTransaction> {
-    let request = SwapPostRequest {
-        route: route.into(),
-        user_public_key,
-        wrap_unwrap_sol: false,
-        fee_account: None,
-        destination_wallet: None,
-        as_legacy_transaction: false,
-    };
-    let raw_swap = jup_api::swap_post(&Configuration::new(get_base_url()), request).await?;
-
-    let decode = |base64_transaction: String| -> Result<VersionedTransaction> {
-        bincode::deserialize(&base64::decode(base64_transaction)?).map_err(|err| err.into())
-    };
-
-    decode(raw_swap.swap_transaction)
+    default_client()
+        .get_swap_transactions(route, user_public_key, options)
+        .await
 }
 
 /// Returns a hash map, input mint as key and an array of valid output mint as values
 pub async fn get_route_map(only_direct_routes: bool) -> Result<RouteMap> {
-    let raw_route_map =
-        jup_api::indexed_route_map_get(&Configuration::new(get_base_url()), only_direct_routes)
-            .await?;
-    Ok(raw_route_map)
+    default_client().get_route_map(only_direct_routes).await
 }
 
 /// Get decompiled instructions but requires a fetcher to retrieve the lookup tables
@@ -118,44 +164,66 @@ pub async fn get_route_map(only_direct_routes: bool) -> Result<RouteMap> {
 pub async fn get_swap_instructions(
     route: impl Into<Box<SwapRoute>>,
     user_public_key: Pubkey,
+    options: &SwapOptions,
     accounts_fetcher: &impl AsyncAccountFetcher,
 ) -> Result<DecompiledVersionedTx> {
-    let transactions = get_swap_transactions(route, user_public_key).await?;
-    decompile_transaction_instructions_with_async_fetcher(transactions, accounts_fetcher).await
+    default_client()
+        .get_swap_instructions(route, user_public_key, options, accounts_fetcher)
+        .await
+}
+
+/// Get the swap instructions directly from the swap-instructions endpoint, only the lookup
+/// tables are fetched with the provided fetcher
+pub async fn get_swap_instruction_set(
+    route: impl Into<Box<SwapRoute>>,
+    user_public_key: Pubkey,
+    options: &SwapOptions,
+    accounts_fetcher: &impl AsyncAccountFetcher,
+) -> Result<SwapInstructionSet> {
+    default_client()
+        .get_swap_instruction_set(route, user_public_key, options, accounts_fetcher)
+        .await
 }
 
-#[allow(clippy::too_many_arguments)]
 /// Get the swap instructions for the best route matching parameters
 pub async fn get_best_swap_instructions(
-    input_mint: &Pubkey,
-    output_mint: &Pubkey,
-    amount: u64,
-    only_direct_routes: bool,
-    slippage_bps: Option<u16>,
-    price_impact_limit: Option<f32>,
-    max_accounts: Option<u8>,
+    request: &QuoteRequest,
+    price_impact_limit: Option<Decimal>,
     user_public_key: Pubkey,
+    options: &SwapOptions,
     accounts_fetcher: &impl AsyncAccountFetcher,
 ) -> Result<DecompiledVersionedTx> {
-    let best_route = get_quote(
-        input_mint,
-        output_mint,
-        amount,
-        only_direct_routes,
-        slippage_bps,
-        max_accounts,
-    )
-    .await?;
-
-    use crate::Error::ResponseTypeConversionError;
-    let route_price_impact_pct: f32 = best_route
-        .price_impact_pct
-        .parse::<f32>()
-        .map_err(|_| ResponseTypeConversionError)?;
-    if let Some(price_impact_limit) = price_impact_limit {
-        if route_price_impact_pct > price_impact_limit {
-            return Err(Error::PriceImpactTooHigh(route_price_impact_pct));
-        }
-    }
-    get_swap_instructions(best_route, user_public_key, accounts_fetcher).await
+    default_client()
+        .get_best_swap_instructions(
+            request,
+            price_impact_limit,
+            user_public_key,
+            options,
+            accounts_fetcher,
+        )
+        .await
+}
+
+/// Get a swap transaction wrapped between the instructions of `composer`, re-quoting with
+/// a smaller `max_accounts` until it fits
+pub async fn get_fitting_swap_transaction(
+    request: &QuoteRequest,
+    price_impact_limit: Option<Decimal>,
+    user_public_key: Pubkey,
+    options: &SwapOptions,
+    composer: &TxComposer,
+    account_lock_limit: usize,
+    accounts_fetcher: &impl AsyncAccountFetcher,
+) -> Result<FittedSwap> {
+    default_client()
+        .get_fitting_swap_transaction(
+            request,
+            price_impact_limit,
+            user_public_key,
+            options,
+            composer,
+            account_lock_limit,
+            accounts_fetcher,
+        )
+        .await
 }
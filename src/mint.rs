@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use rust_decimal::Decimal;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::errors::{Error, Result};
+use crate::utils::AsyncAccountFetcher;
+
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+// Base mint layout shared by SPL Token and Token-2022, extensions come after it
+const MINT_LEN: usize = 82;
+const DECIMALS_OFFSET: usize = 44;
+const IS_INITIALIZED_OFFSET: usize = 45;
+
+/// Read the decimals of a SPL Token or Token-2022 mint account
+pub fn mint_decimals(mint: &Pubkey, account: &Account) -> Result<u8> {
+    if account.owner != TOKEN_PROGRAM_ID && account.owner != TOKEN_2022_PROGRAM_ID {
+        return Err(Error::InvalidMintAccount(*mint));
+    }
+    match account.data.get(..MINT_LEN) {
+        Some(data) if data[IS_INITIALIZED_OFFSET] == 1 => Ok(data[DECIMALS_OFFSET]),
+        _ => Err(Error::InvalidMintAccount(*mint)),
+    }
+}
+
+/// Convert a raw token amount into its decimal UI representation
+pub fn amount_to_ui_amount(amount: u64, decimals: u8) -> Result<Decimal> {
+    Decimal::try_from_i128_with_scale(amount.into(), decimals.into())
+        .map_err(|_| Error::InvalidUiAmount(amount.into()))
+}
+
+/// Convert a decimal UI amount into a raw token amount
+///
+/// Amounts that are negative, too large or more precise than the mint decimals are rejected.
+pub fn ui_amount_to_amount(ui_amount: Decimal, decimals: u8) -> Result<u64> {
+    let invalid = || Error::InvalidUiAmount(ui_amount);
+    let normalized = ui_amount.normalize();
+    if normalized.is_sign_negative() || normalized.scale() > u32::from(decimals) {
+        return Err(invalid());
+    }
+    10i128
+        .checked_pow(u32::from(decimals) - normalized.scale())
+        .and_then(|factor| normalized.mantissa().checked_mul(factor))
+        .and_then(|amount| u64::try_from(amount).ok())
+        .ok_or_else(invalid)
+}
+
+/// Cache of mint decimals, they never change once a mint is initialized
+#[derive(Debug, Default)]
+pub struct MintDecimalsCache {
+    decimals: RwLock<HashMap<Pubkey, u8>>,
+}
+
+impl MintDecimalsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, mint: &Pubkey) -> Option<u8> {
+        self.read().get(mint).copied()
+    }
+
+    pub fn insert(&self, mint: Pubkey, decimals: u8) {
+        self.write().insert(mint, decimals);
+    }
+
+    /// Decimals of the given mints, only the mints missing from the cache are fetched
+    pub async fn get_or_fetch(
+        &self,
+        mints: &[Pubkey],
+        accounts_fetcher: &impl AsyncAccountFetcher,
+    ) -> Result<Vec<u8>> {
+        let mut missing = mints
+            .iter()
+            .filter(|mint| self.get(mint).is_none())
+            .copied()
+            .collect::<Vec<_>>();
+        missing.sort_unstable();
+        missing.dedup();
+        if !missing.is_empty() {
+            let accounts = accounts_fetcher.fech_accounts(&missing).await?;
+            for (mint, account) in missing.iter().zip(accounts) {
+                let account = account.ok_or(Error::MintAccountNotFound(*mint))?;
+                self.insert(*mint, mint_decimals(mint, &account)?);
+            }
+        }
+        mints
+            .iter()
+            .map(|mint| self.get(mint).ok_or(Error::MintAccountNotFound(*mint)))
+            .collect()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<Pubkey, u8>> {
+        self.decimals.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<Pubkey, u8>> {
+        self.decimals.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,87 @@
+use serde_with::skip_serializing_none;
+
+/// ApiErrorResponse : Error body returned by the Jupiter API on 4xx and 5xx statuses
+#[skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiErrorResponse {
+    /// Human readable error message
+    #[serde(alias = "message")]
+    pub error: Option<String>,
+    /// Machine readable error code
+    pub error_code: Option<JupiterErrorCode>,
+}
+
+/// Error codes returned by the Jupiter API in `errorCode`
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum JupiterErrorCode {
+    CouldNotFindAnyRoute,
+    NoRoutesFound,
+    RoutePlanDoesNotConsumeAllTheAmount,
+    TokenNotTradable,
+    CircularArbitrageIsDisabled,
+    MarketNotFound,
+    InvalidRequest,
+    /// Any code not known by this crate
+    Other(String),
+}
+
+impl JupiterErrorCode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::CouldNotFindAnyRoute => "COULD_NOT_FIND_ANY_ROUTE",
+            Self::NoRoutesFound => "NO_ROUTES_FOUND",
+            Self::RoutePlanDoesNotConsumeAllTheAmount => {
+                "ROUTE_PLAN_DOES_NOT_CONSUME_ALL_THE_AMOUNT"
+            }
+            Self::TokenNotTradable => "TOKEN_NOT_TRADABLE",
+            Self::CircularArbitrageIsDisabled => "CIRCULAR_ARBITRAGE_IS_DISABLED",
+            Self::MarketNotFound => "MARKET_NOT_FOUND",
+            Self::InvalidRequest => "INVALID_REQUEST",
+            Self::Other(code) => code,
+        }
+    }
+
+    /// Whether the code means that no route exists for the requested swap
+    pub fn is_no_route(&self) -> bool {
+        matches!(
+            self,
+            Self::CouldNotFindAnyRoute
+                | Self::NoRoutesFound
+                | Self::RoutePlanDoesNotConsumeAllTheAmount
+        )
+    }
+}
+
+impl From<String> for JupiterErrorCode {
+    fn from(code: String) -> Self {
+        match code.as_str() {
+            "COULD_NOT_FIND_ANY_ROUTE" => Self::CouldNotFindAnyRoute,
+            "NO_ROUTES_FOUND" => Self::NoRoutesFound,
+            "ROUTE_PLAN_DOES_NOT_CONSUME_ALL_THE_AMOUNT" => {
+                Self::RoutePlanDoesNotConsumeAllTheAmount
+            }
+            "TOKEN_NOT_TRADABLE" => Self::TokenNotTradable,
+            "CIRCULAR_ARBITRAGE_IS_DISABLED" => Self::CircularArbitrageIsDisabled,
+            "MARKET_NOT_FOUND" => Self::MarketNotFound,
+            "INVALID_REQUEST" => Self::InvalidRequest,
+            _ => Self::Other(code),
+        }
+    }
+}
+
+impl From<JupiterErrorCode> for String {
+    fn from(code: JupiterErrorCode) -> Self {
+        match code {
+            JupiterErrorCode::Other(code) => code,
+            code => code.as_str().to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Display for JupiterErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,23 @@
+use std::collections::HashMap;
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::models::SwapPrice;
+
+/// BulkPrices : Prices of many mints fetched in several requests
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct BulkPrices {
+    pub prices: HashMap<Pubkey, SwapPrice>,
+    /// Requested mints the API returned no price for, in request order
+    pub missing: Vec<Pubkey>,
+}
+
+impl BulkPrices {
+    pub fn get(&self, mint: &Pubkey) -> Option<&SwapPrice> {
+        self.prices.get(mint)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
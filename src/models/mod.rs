@@ -1,3 +1,5 @@
+pub mod api_error_response;
+pub use self::api_error_response::{ApiErrorResponse, JupiterErrorCode};
 pub mod market_info;
 pub use self::market_info::*;
 pub mod swap_route;
@@ -8,11 +10,24 @@ pub mod indexed_route_map_200_response;
 pub use self::indexed_route_map_200_response::RouteMap;
 pub mod price_get_200_response;
 pub use self::price_get_200_response::PriceGet200Response;
+pub mod bulk_prices;
+pub use self::bulk_prices::BulkPrices;
+pub mod price_id;
+pub use self::price_id::PriceId;
+pub mod price_v2;
+pub use self::price_v2::{PriceV2, PriceV2Response};
 pub mod price_get_409_response;
 pub use self::price_get_409_response::PriceGet409Response;
 pub mod quote_get_200_response;
 pub use self::quote_get_200_response::QuoteGet200Response;
 pub mod swap_post_200_response;
 pub use self::swap_post_200_response::SwapPost200Response;
+pub mod quote_request;
+pub(crate) mod serde_helpers;
+pub use self::quote_request::QuoteRequest;
+pub mod swap_options;
+pub use self::swap_options::SwapOptions;
+pub mod swap_instructions_post_200_response;
+pub use self::swap_instructions_post_200_response::SwapInstructionsPost200Response;
 pub mod swap_post_request;
 pub use self::swap_post_request::SwapPostRequest;
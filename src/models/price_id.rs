@@ -0,0 +1,54 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use solana_sdk::pubkey::Pubkey;
+
+/// PriceId : Token looked up by the price API, either by mint address or by symbol
+///
+/// Symbols are not unique, an ambiguous symbol is rejected with the candidate addresses.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PriceId {
+    Mint(Pubkey),
+    Symbol(String),
+}
+
+impl PriceId {
+    pub fn symbol(symbol: impl Into<String>) -> Self {
+        Self::Symbol(symbol.into())
+    }
+
+    pub fn mint(&self) -> Option<&Pubkey> {
+        match self {
+            Self::Mint(mint) => Some(mint),
+            Self::Symbol(_) => None,
+        }
+    }
+}
+
+impl From<Pubkey> for PriceId {
+    fn from(mint: Pubkey) -> Self {
+        Self::Mint(mint)
+    }
+}
+
+/// Valid addresses are parsed as mints, anything else is a symbol
+impl FromStr for PriceId {
+    type Err = Infallible;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        Ok(id
+            .parse()
+            .map(Self::Mint)
+            .unwrap_or_else(|_| Self::symbol(id)))
+    }
+}
+
+impl fmt::Display for PriceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mint(mint) => mint.fmt(f),
+            Self::Symbol(symbol) => f.write_str(symbol),
+        }
+    }
+}
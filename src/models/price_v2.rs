@@ -0,0 +1,144 @@
+use std::collections::{BTreeMap, HashMap};
+
+use rust_decimal::Decimal;
+use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::models::serde_helpers::DecimalFromJsonNumber;
+
+/// PriceV2Response : Response of the price API v2, ids without a price are mapped to `null`
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceV2Response {
+    pub data: HashMap<String, Option<PriceV2>>,
+    pub time_taken: Option<f64>,
+}
+
+/// Price of a token with the optional details of how it was derived
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceV2 {
+    /// Address of the token
+    #[serde_as(as = "DisplayFromStr")]
+    pub id: Pubkey,
+    /// How the price was computed, e.g. `derivedPrice`
+    #[serde(rename = "type")]
+    pub price_type: Option<String>,
+    /// Price of 1 unit of the token in the vs token
+    #[serde_as(as = "DecimalFromJsonNumber")]
+    pub price: Decimal,
+    /// Only returned when requested with `showExtraInfo`
+    pub extra_info: Option<PriceExtraInfo>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceExtraInfo {
+    pub last_swapped_price: Option<LastSwappedPrice>,
+    pub quoted_price: Option<QuotedPrice>,
+    pub confidence_level: Option<ConfidenceLevel>,
+    pub depth: Option<PriceDepth>,
+}
+
+/// Prices of the last swaps made through Jupiter, timestamps are unix seconds
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastSwappedPrice {
+    pub last_jupiter_sell_at: Option<i64>,
+    #[serde_as(as = "Option<DecimalFromJsonNumber>")]
+    #[serde(default)]
+    pub last_jupiter_sell_price: Option<Decimal>,
+    pub last_jupiter_buy_at: Option<i64>,
+    #[serde_as(as = "Option<DecimalFromJsonNumber>")]
+    #[serde(default)]
+    pub last_jupiter_buy_price: Option<Decimal>,
+}
+
+/// Prices quoted by Jupiter to buy and sell the token, timestamps are unix seconds
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotedPrice {
+    #[serde_as(as = "Option<DecimalFromJsonNumber>")]
+    #[serde(default)]
+    pub buy_price: Option<Decimal>,
+    pub buy_at: Option<i64>,
+    #[serde_as(as = "Option<DecimalFromJsonNumber>")]
+    #[serde(default)]
+    pub sell_price: Option<Decimal>,
+    pub sell_at: Option<i64>,
+}
+
+/// Confidence of Jupiter in the returned price
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ConfidenceLevel {
+    High,
+    Medium,
+    Low,
+    /// Any level not known by this crate
+    Other(String),
+}
+
+impl ConfidenceLevel {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::High => "high",
+            Self::Medium => "medium",
+            Self::Low => "low",
+            Self::Other(level) => level,
+        }
+    }
+}
+
+impl From<String> for ConfidenceLevel {
+    fn from(level: String) -> Self {
+        match level.as_str() {
+            "high" => Self::High,
+            "medium" => Self::Medium,
+            "low" => Self::Low,
+            _ => Self::Other(level),
+        }
+    }
+}
+
+impl From<ConfidenceLevel> for String {
+    fn from(level: ConfidenceLevel) -> Self {
+        match level {
+            ConfidenceLevel::Other(level) => level,
+            level => level.as_str().to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfidenceLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Price impact ratios of buying and selling the token
+#[skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceDepth {
+    pub buy_price_impact_ratio: Option<PriceImpactDepth>,
+    pub sell_price_impact_ratio: Option<PriceImpactDepth>,
+}
+
+/// Price impact ratio keyed by the swapped value in the vs token, e.g. 10, 100 and 1000
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceImpactDepth {
+    #[serde_as(as = "BTreeMap<DisplayFromStr, DecimalFromJsonNumber>")]
+    pub depth: BTreeMap<u64, Decimal>,
+    pub timestamp: Option<i64>,
+}
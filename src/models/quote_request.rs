@@ -0,0 +1,111 @@
+use serde_with::{
+    serde_as, skip_serializing_none, CommaSeparator, DisplayFromStr, StringWithSeparator,
+};
+use solana_sdk::pubkey::Pubkey;
+use std::time::Duration;
+
+use crate::models::swap_route::SwapMode;
+
+/// QuoteRequest : Query parameters of the quote endpoint
+///
+/// Only the input mint, output mint and amount are mandatory, every other parameter is left
+/// to the API default when not set.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteRequest {
+    /// Mint of the token to swap from
+    #[serde_as(as = "DisplayFromStr")]
+    pub input_mint: Pubkey,
+    /// Mint of the token to swap to
+    #[serde_as(as = "DisplayFromStr")]
+    pub output_mint: Pubkey,
+    /// Raw amount (without decimals), input amount for `ExactIn`, output amount for `ExactOut`
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: u64,
+    /// Slippage tolerance in basis points
+    pub slippage_bps: Option<u16>,
+    /// Whether `amount` is the exact input or the exact output of the swap
+    pub swap_mode: Option<SwapMode>,
+    /// Restrict the route to these dexes only
+    #[serde_as(as = "Option<StringWithSeparator::<CommaSeparator, String>>")]
+    pub dexes: Option<Vec<String>>,
+    /// Never route through these dexes
+    #[serde_as(as = "Option<StringWithSeparator::<CommaSeparator, String>>")]
+    pub exclude_dexes: Option<Vec<String>>,
+    /// Only route through intermediate tokens with highly liquid pairs
+    pub restrict_intermediate_tokens: Option<bool>,
+    /// Only consider single hop routes
+    pub only_direct_routes: Option<bool>,
+    /// Quote a route that fits in a legacy transaction
+    pub as_legacy_transaction: Option<bool>,
+    /// Platform fee in basis points, the fee account must then be passed on swap
+    pub platform_fee_bps: Option<u16>,
+    /// Rough upper bound of accounts used by the route
+    pub max_accounts: Option<u8>,
+    /// Deadline of the whole quote call, retries included, not sent to the API
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+}
+
+impl QuoteRequest {
+    pub fn new(input_mint: Pubkey, output_mint: Pubkey, amount: u64) -> Self {
+        Self {
+            input_mint,
+            output_mint,
+            amount,
+            ..Default::default()
+        }
+    }
+
+    pub fn slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.slippage_bps = Some(slippage_bps);
+        self
+    }
+
+    pub fn swap_mode(mut self, swap_mode: SwapMode) -> Self {
+        self.swap_mode = Some(swap_mode);
+        self
+    }
+
+    pub fn dexes(mut self, dexes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.dexes = Some(dexes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn exclude_dexes(mut self, dexes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.exclude_dexes = Some(dexes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn restrict_intermediate_tokens(mut self, restrict_intermediate_tokens: bool) -> Self {
+        self.restrict_intermediate_tokens = Some(restrict_intermediate_tokens);
+        self
+    }
+
+    pub fn only_direct_routes(mut self, only_direct_routes: bool) -> Self {
+        self.only_direct_routes = Some(only_direct_routes);
+        self
+    }
+
+    pub fn as_legacy_transaction(mut self, as_legacy_transaction: bool) -> Self {
+        self.as_legacy_transaction = Some(as_legacy_transaction);
+        self
+    }
+
+    pub fn platform_fee_bps(mut self, platform_fee_bps: u16) -> Self {
+        self.platform_fee_bps = Some(platform_fee_bps);
+        self
+    }
+
+    pub fn max_accounts(mut self, max_accounts: u8) -> Self {
+        self.max_accounts = Some(max_accounts);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
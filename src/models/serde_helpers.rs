@@ -0,0 +1,58 @@
+use rust_decimal::Decimal;
+use serde::{de, Deserialize, Deserializer, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+
+/// `Decimal` serialized as a string, scientific notation is accepted on deserialization
+pub struct DecimalFromStr;
+
+impl SerializeAs<Decimal> for DecimalFromStr {
+    fn serialize_as<S>(source: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(source)
+    }
+}
+
+impl<'de> DeserializeAs<'de, Decimal> for DecimalFromStr {
+    fn deserialize_as<D>(deserializer: D) -> Result<Decimal, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Decimal::from_str_exact(&value)
+            .or_else(|_| Decimal::from_scientific(&value))
+            .map_err(|err| de::Error::custom(format!("invalid decimal {:?}: {}", value, err)))
+    }
+}
+
+/// `Decimal` read from the raw text of a JSON number or string, never going through floats
+///
+/// Only works with `serde_json`, which is the only format the API speaks. Serialized as a string.
+pub struct DecimalFromJsonNumber;
+
+impl SerializeAs<Decimal> for DecimalFromJsonNumber {
+    fn serialize_as<S>(source: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        DecimalFromStr::serialize_as(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, Decimal> for DecimalFromJsonNumber {
+    fn deserialize_as<D>(deserializer: D) -> Result<Decimal, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = Box::<serde_json::value::RawValue>::deserialize(deserializer)?;
+        let value = raw.get();
+        let value = match serde_json::from_str::<String>(value) {
+            Ok(string) => string,
+            Err(_) => value.to_owned(),
+        };
+        Decimal::from_str_exact(&value)
+            .or_else(|_| Decimal::from_scientific(&value))
+            .map_err(|err| de::Error::custom(format!("invalid decimal {:?}: {}", value, err)))
+    }
+}
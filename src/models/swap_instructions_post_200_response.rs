@@ -0,0 +1,73 @@
+use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+
+/// SwapInstructionsPost200Response : Default response of the swap-instructions endpoint
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapInstructionsPost200Response {
+    /// Only present when `useTokenLedger` is set
+    pub token_ledger_instruction: Option<ApiInstruction>,
+    /// Compute unit limit and price instructions
+    #[serde(default)]
+    pub compute_budget_instructions: Vec<ApiInstruction>,
+    /// Setup instructions, for example the creation of the missing token accounts
+    #[serde(default)]
+    pub setup_instructions: Vec<ApiInstruction>,
+    /// The actual swap instruction
+    pub swap_instruction: ApiInstruction,
+    /// Unwrap the SOL if `wrapAndUnwrapSol` is set
+    pub cleanup_instruction: Option<ApiInstruction>,
+    /// Any other instruction the API requires, for example tips
+    #[serde(default)]
+    pub other_instructions: Vec<ApiInstruction>,
+    /// Lookup tables referenced by the swap
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    #[serde(default)]
+    pub address_lookup_table_addresses: Vec<Pubkey>,
+    /// Prioritization fee included in the compute budget instructions, in lamports
+    pub prioritization_fee_lamports: Option<u64>,
+}
+
+/// Json representation of an instruction, data is base64 encoded
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiInstruction {
+    #[serde_as(as = "DisplayFromStr")]
+    pub program_id: Pubkey,
+    pub accounts: Vec<ApiAccountMeta>,
+    pub data: String,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiAccountMeta {
+    #[serde_as(as = "DisplayFromStr")]
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl TryFrom<ApiInstruction> for Instruction {
+    type Error = base64::DecodeError;
+
+    fn try_from(instruction: ApiInstruction) -> Result<Self, Self::Error> {
+        Ok(Instruction {
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .into_iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: base64::decode(instruction.data)?,
+        })
+    }
+}
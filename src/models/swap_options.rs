@@ -0,0 +1,89 @@
+use solana_sdk::pubkey::Pubkey;
+use std::time::Duration;
+
+/// SwapOptions : Optional parameters of the swap endpoint
+///
+/// The default matches the historical behavior of this crate: no SOL wrapping, versioned
+/// transaction and every other parameter left to the API default.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SwapOptions {
+    /// Wrap/unwrap SOL
+    pub wrap_and_unwrap_sol: bool,
+    /// Fee token account for the output token (only pass in if you set a platformFeeBps)
+    pub fee_account: Option<Pubkey>,
+    /// Public key of the wallet that will receive the output of the swap
+    pub destination_wallet: Option<Pubkey>,
+    /// Token account that will receive the output of the swap, must already exist
+    pub destination_token_account: Option<Pubkey>,
+    /// Request a legacy transaction rather than a versioned transaction
+    pub as_legacy_transaction: bool,
+    /// Compute unit price of the swap transaction, in micro lamports
+    pub compute_unit_price_micro_lamports: Option<u64>,
+    /// Total prioritization fee of the swap transaction, in lamports
+    pub prioritization_fee_lamports: Option<u64>,
+    /// Simulate the swap to set the compute unit limit instead of using the maximum
+    pub dynamic_compute_unit_limit: Option<bool>,
+    /// Use the shared program accounts, no intermediate token accounts are then needed
+    pub use_shared_accounts: Option<bool>,
+    /// Swap the difference of the input token account balance recorded by a token ledger
+    pub use_token_ledger: Option<bool>,
+    /// Deadline of the whole swap call, retries included
+    pub timeout: Option<Duration>,
+}
+
+impl SwapOptions {
+    pub fn wrap_and_unwrap_sol(mut self, wrap_and_unwrap_sol: bool) -> Self {
+        self.wrap_and_unwrap_sol = wrap_and_unwrap_sol;
+        self
+    }
+
+    pub fn fee_account(mut self, fee_account: Pubkey) -> Self {
+        self.fee_account = Some(fee_account);
+        self
+    }
+
+    pub fn destination_wallet(mut self, destination_wallet: Pubkey) -> Self {
+        self.destination_wallet = Some(destination_wallet);
+        self
+    }
+
+    pub fn destination_token_account(mut self, destination_token_account: Pubkey) -> Self {
+        self.destination_token_account = Some(destination_token_account);
+        self
+    }
+
+    pub fn as_legacy_transaction(mut self, as_legacy_transaction: bool) -> Self {
+        self.as_legacy_transaction = as_legacy_transaction;
+        self
+    }
+
+    pub fn compute_unit_price_micro_lamports(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price_micro_lamports = Some(micro_lamports);
+        self
+    }
+
+    pub fn prioritization_fee_lamports(mut self, lamports: u64) -> Self {
+        self.prioritization_fee_lamports = Some(lamports);
+        self
+    }
+
+    pub fn dynamic_compute_unit_limit(mut self, dynamic_compute_unit_limit: bool) -> Self {
+        self.dynamic_compute_unit_limit = Some(dynamic_compute_unit_limit);
+        self
+    }
+
+    pub fn use_shared_accounts(mut self, use_shared_accounts: bool) -> Self {
+        self.use_shared_accounts = Some(use_shared_accounts);
+        self
+    }
+
+    pub fn use_token_ledger(mut self, use_token_ledger: bool) -> Self {
+        self.use_token_ledger = Some(use_token_ledger);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
@@ -25,4 +25,46 @@ pub struct SwapPostRequest {
     #[serde(rename = "destinationWallet")]
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub destination_wallet: Option<Pubkey>,
+    /// Token account that will receive the output of the swap, it must already exist
+    #[serde(rename = "destinationTokenAccount")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub destination_token_account: Option<Pubkey>,
+    /// Compute unit price to prioritize the transaction, in micro lamports
+    #[serde(rename = "computeUnitPriceMicroLamports")]
+    pub compute_unit_price_micro_lamports: Option<u64>,
+    /// Total prioritization fee of the transaction, in lamports
+    #[serde(rename = "prioritizationFeeLamports")]
+    pub prioritization_fee_lamports: Option<u64>,
+    /// Simulate the swap to set the compute unit limit instead of using the maximum
+    #[serde(rename = "dynamicComputeUnitLimit")]
+    pub dynamic_compute_unit_limit: Option<bool>,
+    /// Use the shared program accounts, intermediate token accounts are then not needed
+    #[serde(rename = "useSharedAccounts")]
+    pub use_shared_accounts: Option<bool>,
+    /// Swap the difference of the input token account balance recorded by a token ledger
+    #[serde(rename = "useTokenLedger")]
+    pub use_token_ledger: Option<bool>,
+}
+
+impl SwapPostRequest {
+    pub fn new(
+        route: impl Into<Box<crate::models::SwapRoute>>,
+        user_public_key: Pubkey,
+        options: &crate::models::SwapOptions,
+    ) -> Self {
+        Self {
+            route: route.into(),
+            user_public_key,
+            wrap_unwrap_sol: options.wrap_and_unwrap_sol,
+            fee_account: options.fee_account,
+            as_legacy_transaction: options.as_legacy_transaction,
+            destination_wallet: options.destination_wallet,
+            destination_token_account: options.destination_token_account,
+            compute_unit_price_micro_lamports: options.compute_unit_price_micro_lamports,
+            prioritization_fee_lamports: options.prioritization_fee_lamports,
+            dynamic_compute_unit_limit: options.dynamic_compute_unit_limit,
+            use_shared_accounts: options.use_shared_accounts,
+            use_token_ledger: options.use_token_ledger,
+        }
+    }
 }
@@ -1,9 +1,13 @@
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
 use solana_sdk::pubkey::Pubkey;
 
+use crate::models::serde_helpers::DecimalFromJsonNumber;
+
 #[serde_as]
 #[skip_serializing_none]
-#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct SwapPrice {
     /// Address of the token
     #[serde_as(as = "DisplayFromStr")]
@@ -20,6 +24,21 @@ pub struct SwapPrice {
     #[serde(rename = "vsTokenSymbol")]
     pub vs_token_symbol: Option<String>,
     /// Default to 1 unit of the token worth in USDC if vsToken is not specified.
+    ///
+    /// Parsed from the raw JSON number, without any float rounding.
+    #[serde_as(as = "DecimalFromJsonNumber")]
     #[serde(rename = "price")]
-    pub price: f32,
+    pub price: Decimal,
+}
+
+impl SwapPrice {
+    /// Price as `f64`, lossy
+    pub fn price_f64(&self) -> f64 {
+        self.price.to_f64().unwrap_or(f64::NAN)
+    }
+
+    /// Price as `f32` like it used to be exposed, lossy
+    pub fn price_f32(&self) -> f32 {
+        self.price.to_f32().unwrap_or(f32::NAN)
+    }
 }
@@ -1,17 +1,32 @@
+use rust_decimal::Decimal;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::value::RawValue;
 use serde_with::{serde_as, DisplayFromStr};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
 
+use crate::mint::amount_to_ui_amount;
+use crate::models::serde_helpers::DecimalFromStr;
+
+/// Quote returned by the API, to be echoed back verbatim to the swap endpoints
+///
+/// A deserialized route keeps the JSON it was read from and serializes to it byte for byte as
+/// long as its fields are left untouched. Mutating any field drops that JSON: the route is then
+/// serialized from its fields, followed by `extra` which keeps the fields not modelled here.
+/// Routes built by hand start from `..SwapRoute::default()`.
 #[serde_as]
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct SwapRoute {
     #[serde_as(as = "DisplayFromStr")]
     #[serde(rename = "inputMint")]
-    pub input_mint: String,
+    pub input_mint: Pubkey,
     #[serde_as(as = "DisplayFromStr")]
     #[serde(rename = "inAmount")]
     pub in_amount: u64,
     #[serde_as(as = "DisplayFromStr")]
     #[serde(rename = "outputMint")]
-    pub output_mint: String,
+    pub output_mint: Pubkey,
     #[serde_as(as = "DisplayFromStr")]
     #[serde(rename = "outAmount")]
     pub out_amount: u64,
@@ -25,15 +40,68 @@ pub struct SwapRoute {
     #[serde(rename = "slippageBps")]
     pub slippage_bps: i32,
     #[serde(rename = "platformFee")]
-    pub fees: Option<i32>,
+    pub platform_fee: Option<PlatformFee>,
+    #[serde_as(as = "DecimalFromStr")]
     #[serde(rename = "priceImpactPct")]
-    pub price_impact_pct: String,
+    pub price_impact_pct: Decimal,
     #[serde(rename = "routePlan")]
     pub route_plan: Vec<RoutePlan>,
     #[serde(rename = "contextSlot")]
     pub context_slot: u64,
     #[serde(rename = "timeTaken")]
-    pub time_taken: f32,
+    pub time_taken: f64,
+    /// Fields not modelled by this crate
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+    /// Quote as received from the API, empty for routes built by hand
+    #[serde(skip)]
+    pub raw: RawQuote,
+}
+
+impl<'de> Deserialize<'de> for SwapRoute {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = Box::<RawValue>::deserialize(deserializer)?;
+        let mut route = SwapRoute::deserialize(&mut serde_json::Deserializer::from_str(raw.get()))
+            .map_err(de::Error::custom)?;
+        route.raw = RawQuote(Some(raw));
+        Ok(route)
+    }
+}
+
+impl Serialize for SwapRoute {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.unmodified_raw() {
+            Some(raw) => raw.serialize(serializer),
+            None => SwapRoute::serialize(self, serializer),
+        }
+    }
+}
+
+impl SwapRoute {
+    /// The JSON the route was read from, unless a field was mutated since
+    fn unmodified_raw(&self) -> Option<&RawValue> {
+        let raw = self.raw.0.as_deref()?;
+        let mut parsed =
+            SwapRoute::deserialize(&mut serde_json::Deserializer::from_str(raw.get())).ok()?;
+        parsed.raw = self.raw.clone();
+        (parsed == *self).then_some(raw)
+    }
+}
+
+/// JSON text of a deserialized quote, compared as text
+#[derive(Clone, Debug, Default)]
+pub struct RawQuote(Option<Box<RawValue>>);
+
+impl PartialEq for RawQuote {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ref().map(|raw| raw.get()) == other.0.as_ref().map(|raw| raw.get())
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -41,55 +109,107 @@ pub struct SwapRoute {
 pub struct RoutePlan {
     pub swap_info: SwapInfo,
     pub percent: i64,
+    /// Fields not modelled by this crate
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
+#[serde_as]
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapInfo {
-    pub amm_key: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub amm_key: Pubkey,
     pub label: String,
-    pub input_mint: String,
-    pub output_mint: String,
-    pub in_amount: String,
-    pub out_amount: String,
-    pub fee_amount: String,
-    pub fee_mint: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub input_mint: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub output_mint: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub in_amount: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub out_amount: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub fee_amount: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub fee_mint: Pubkey,
+    /// Fields not modelled by this crate
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Swap mode
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize,
+)]
 pub enum SwapMode {
+    #[default]
     ExactIn,
     ExactOut,
 }
 
-impl Default for SwapMode {
-    fn default() -> SwapMode {
-        Self::ExactIn
+/// Platform fee charged on top of the route, see `platformFeeBps` in the quote request
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformFee {
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: u64,
+    pub fee_bps: u16,
+    /// Fields not modelled by this crate
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Fees paid along a route, per mint
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct FeeBreakdown {
+    /// LP fees of every step of the route plan, summed per fee mint
+    pub lp_fees: BTreeMap<Pubkey, u64>,
+    /// Platform fee mint and amount: the output mint for `ExactIn`, the input mint for `ExactOut`
+    pub platform_fee: Option<(Pubkey, u64)>,
+}
+
+impl FeeBreakdown {
+    /// All the fees, LP and platform, summed per mint
+    pub fn total_per_mint(&self) -> BTreeMap<Pubkey, u64> {
+        let mut totals = self.lp_fees.clone();
+        if let Some((mint, amount)) = self.platform_fee {
+            let total = totals.entry(mint).or_default();
+            *total = total.saturating_add(amount);
+        }
+        totals
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
-pub struct Fees {
-    /// This inidicate the total amount needed for signing transaction(s). Value in lamports.
-    #[serde(rename = "signatureFee")]
-    pub signature_fee: Option<f32>,
-    /// This inidicate the total amount needed for deposit of serum order account(s). Value in lamports.
-    #[serde(rename = "openOrdersDeposits")]
-    pub open_orders_deposits: Option<Vec<f32>>,
-    /// This inidicate the total amount needed for deposit of associative token account(s). Value in lamports.
-    #[serde(rename = "ataDeposits")]
-    pub ata_deposits: Option<Vec<f32>>,
-    /// This inidicate the total lamports needed for fees and deposits above.
-    #[serde(
-        rename = "totalFeeAndDeposits",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub total_fee_and_deposits: Option<f32>,
-    /// This inidicate the minimum lamports needed for transaction(s). Might be used to create wrapped SOL and will be returned when the wrapped SOL is closed.
-    #[serde(
-        rename = "minimumSOLForTransaction",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub minimum_sol_for_transaction: Option<f32>,
+impl SwapRoute {
+    /// Input amount in UI units, given the decimals of the input mint
+    pub fn ui_in_amount(&self, input_decimals: u8) -> crate::Result<Decimal> {
+        amount_to_ui_amount(self.in_amount, input_decimals)
+    }
+
+    /// Output amount in UI units, given the decimals of the output mint
+    pub fn ui_out_amount(&self, output_decimals: u8) -> crate::Result<Decimal> {
+        amount_to_ui_amount(self.out_amount, output_decimals)
+    }
+
+    /// Fees paid along the route, LP fees from the route plan and the platform fee
+    pub fn fee_breakdown(&self) -> FeeBreakdown {
+        let mut lp_fees = BTreeMap::new();
+        for plan in &self.route_plan {
+            let total: &mut u64 = lp_fees.entry(plan.swap_info.fee_mint).or_default();
+            *total = total.saturating_add(plan.swap_info.fee_amount);
+        }
+        let platform_fee = self.platform_fee.as_ref().map(|fee| {
+            let mint = match self.swap_mode {
+                SwapMode::ExactIn => self.output_mint,
+                SwapMode::ExactOut => self.input_mint,
+            };
+            (mint, fee.amount)
+        });
+        FeeBreakdown {
+            lp_fees,
+            platform_fee,
+        }
+    }
 }
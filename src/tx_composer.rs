@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use solana_program::hash::Hash;
+use solana_program::message::v0;
+use solana_program::message::VersionedMessage;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
+use solana_sdk::compute_budget::{self, ComputeBudgetInstruction};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::VersionedTransaction;
+
+use crate::errors::Result;
+use crate::models::SwapRoute;
+use crate::tx_size::{self, TxSizeEstimate};
+use crate::utils::{DecompiledVersionedTx, SwapInstructionSet};
+
+/// Swap that fits in a transaction with the caller instructions, see
+/// `JupiterClient::get_fitting_swap_transaction`
+#[derive(Debug, Clone)]
+pub struct FittedSwap {
+    /// Route of the last quote, the one used by the transaction
+    pub route: SwapRoute,
+    pub instruction_set: SwapInstructionSet,
+    /// Caller instructions with the swap, to build a signed transaction
+    pub composer: TxComposer,
+    pub estimate: TxSizeEstimate,
+    /// Unsigned transaction with the latest blockhash of the fetcher
+    pub transaction: VersionedTransaction,
+}
+
+/// Builds a v0 transaction wrapping swap instructions between the caller instructions
+///
+/// Compute budget instructions of every part are merged into one of each kind, keeping the
+/// largest value, and put first. Lookup tables of the caller and of the swap are merged.
+#[derive(Debug, Clone)]
+pub struct TxComposer {
+    fee_payer: Pubkey,
+    pre_instructions: Vec<Instruction>,
+    swap_instructions: Vec<Instruction>,
+    post_instructions: Vec<Instruction>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+impl TxComposer {
+    /// `fee_payer` does not have to be the swap user, it must then sign the transaction too
+    pub fn new(fee_payer: Pubkey) -> Self {
+        Self {
+            fee_payer,
+            pre_instructions: Vec::new(),
+            swap_instructions: Vec::new(),
+            post_instructions: Vec::new(),
+            lookup_tables: Vec::new(),
+        }
+    }
+
+    /// Instructions executed before the swap
+    pub fn pre_instructions(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.pre_instructions.extend(instructions);
+        self
+    }
+
+    /// Swap instructions and lookup tables, e.g. from `get_swap_instructions`
+    pub fn swap(mut self, swap: impl Into<DecompiledVersionedTx>) -> Self {
+        let swap = swap.into();
+        self.swap_instructions.extend(swap.instructions);
+        self.lookup_tables
+            .extend(swap.lookup_tables.unwrap_or_default());
+        self
+    }
+
+    /// Instructions executed after the swap
+    pub fn post_instructions(
+        mut self,
+        instructions: impl IntoIterator<Item = Instruction>,
+    ) -> Self {
+        self.post_instructions.extend(instructions);
+        self
+    }
+
+    /// Lookup tables of the caller instructions
+    pub fn lookup_tables(
+        mut self,
+        lookup_tables: impl IntoIterator<Item = AddressLookupTableAccount>,
+    ) -> Self {
+        self.lookup_tables.extend(lookup_tables);
+        self
+    }
+
+    pub fn fee_payer(&self) -> &Pubkey {
+        &self.fee_payer
+    }
+
+    /// All the instructions of the transaction, merged compute budget instructions first
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let all_instructions = self
+            .pre_instructions
+            .iter()
+            .chain(&self.swap_instructions)
+            .chain(&self.post_instructions);
+        let (budget_instructions, instructions): (Vec<_>, Vec<_>) =
+            all_instructions.partition(|ix| ix.program_id == compute_budget::id());
+
+        let mut budget = ComputeBudget::default();
+        let mut unknown_budget_instructions: Vec<Instruction> = Vec::new();
+        for ix in budget_instructions {
+            if !budget.merge(ix) && !unknown_budget_instructions.contains(ix) {
+                unknown_budget_instructions.push(ix.clone());
+            }
+        }
+        budget
+            .instructions()
+            .into_iter()
+            .chain(unknown_budget_instructions)
+            .chain(instructions.into_iter().cloned())
+            .collect()
+    }
+
+    /// Lookup tables deduplicated by key, the copy with the most addresses is kept
+    pub fn merged_lookup_tables(&self) -> Vec<AddressLookupTableAccount> {
+        let mut tables = Vec::<AddressLookupTableAccount>::new();
+        let mut positions = HashMap::<Pubkey, usize>::new();
+        for table in &self.lookup_tables {
+            match positions.get(&table.key) {
+                Some(&position) => {
+                    let kept = &mut tables[position];
+                    if table.addresses.len() > kept.addresses.len() {
+                        *kept = table.clone();
+                    }
+                }
+                None => {
+                    positions.insert(table.key, tables.len());
+                    tables.push(table.clone());
+                }
+            }
+        }
+        tables
+    }
+
+    /// Estimate the size and accounts of the transaction without compiling it, see
+    /// `MAX_TX_ACCOUNT_LOCKS` and `LEGACY_TX_ACCOUNT_LOCKS` for `account_lock_limit`
+    pub fn estimate(&self, account_lock_limit: usize) -> TxSizeEstimate {
+        let other_accounts = self
+            .pre_instructions
+            .iter()
+            .chain(&self.post_instructions)
+            .flat_map(|ix| {
+                ix.accounts
+                    .iter()
+                    .map(|meta| meta.pubkey)
+                    .chain(std::iter::once(ix.program_id))
+            })
+            .chain(std::iter::once(compute_budget::id()))
+            .collect();
+        tx_size::estimate(
+            &self.fee_payer,
+            &self.instructions(),
+            &self.merged_lookup_tables(),
+            &other_accounts,
+            account_lock_limit,
+        )
+    }
+
+    pub fn compile(&self, recent_blockhash: Hash) -> Result<VersionedMessage> {
+        let message = v0::Message::try_compile(
+            &self.fee_payer,
+            &self.instructions(),
+            &self.merged_lookup_tables(),
+            recent_blockhash,
+        )?;
+        Ok(VersionedMessage::V0(message))
+    }
+
+    /// Transaction with default signatures, to be signed elsewhere
+    pub fn build_unsigned(&self, recent_blockhash: Hash) -> Result<VersionedTransaction> {
+        let message = self.compile(recent_blockhash)?;
+        let tx = VersionedTransaction {
+            signatures: vec![
+                Signature::default();
+                usize::from(message.header().num_required_signatures)
+            ],
+            message,
+        };
+        tx_size::check_size(&tx)?;
+        Ok(tx)
+    }
+
+    /// Transaction signed by `signers`, which must hold the fee payer and every other signer
+    pub fn build_signed<T: Signers + ?Sized>(
+        &self,
+        recent_blockhash: Hash,
+        signers: &T,
+    ) -> Result<VersionedTransaction> {
+        let tx = VersionedTransaction::try_new(self.compile(recent_blockhash)?, signers)?;
+        tx_size::check_size(&tx)?;
+        Ok(tx)
+    }
+}
+
+/// Largest value requested for each kind of compute budget instruction
+#[derive(Debug, Default)]
+struct ComputeBudget {
+    heap_frame: Option<u32>,
+    unit_limit: Option<u32>,
+    unit_price: Option<u64>,
+    loaded_accounts_data_size_limit: Option<u32>,
+}
+
+impl ComputeBudget {
+    /// Returns false if the instruction is not a known compute budget instruction
+    fn merge(&mut self, ix: &Instruction) -> bool {
+        // Borsh layout: one byte variant index followed by the little endian value
+        let Some((kind, value)) = ix.data.split_first() else {
+            return false;
+        };
+        if let Ok(value) = <[u8; 4]>::try_from(value) {
+            let field = match kind {
+                1 => &mut self.heap_frame,
+                2 => &mut self.unit_limit,
+                4 => &mut self.loaded_accounts_data_size_limit,
+                _ => return false,
+            };
+            *field = (*field).max(Some(u32::from_le_bytes(value)));
+        } else if let (3, Ok(value)) = (kind, <[u8; 8]>::try_from(value)) {
+            self.unit_price = self.unit_price.max(Some(u64::from_le_bytes(value)));
+        } else {
+            return false;
+        }
+        true
+    }
+
+    fn instructions(&self) -> Vec<Instruction> {
+        self.unit_limit
+            .map(ComputeBudgetInstruction::set_compute_unit_limit)
+            .into_iter()
+            .chain(
+                self.unit_price
+                    .map(ComputeBudgetInstruction::set_compute_unit_price),
+            )
+            .chain(
+                self.heap_frame
+                    .map(ComputeBudgetInstruction::request_heap_frame),
+            )
+            .chain(
+                self.loaded_accounts_data_size_limit
+                    .map(ComputeBudgetInstruction::set_loaded_accounts_data_size_limit),
+            )
+            .collect()
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+
+use solana_program::pubkey::Pubkey;
+use solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::transaction::VersionedTransaction;
+pub use solana_sdk::transaction::MAX_TX_ACCOUNT_LOCKS;
+
+use crate::errors::{Error, Result};
+
+/// Account lock limit of a transaction before it was raised to `MAX_TX_ACCOUNT_LOCKS`
+pub const LEGACY_TX_ACCOUNT_LOCKS: usize = 64;
+
+const SIGNATURE_LEN: usize = 64;
+const PUBKEY_LEN: usize = 32;
+
+/// Size and accounts of a v0 transaction, computed without compiling it
+///
+/// Lookup tables are used the way `v0::Message::try_compile` does: in order, for the accounts
+/// that are neither signers nor invoked programs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxSizeEstimate {
+    /// Serialized size of the signed transaction
+    pub size: usize,
+    /// Bytes left before `PACKET_DATA_SIZE`, negative if the transaction does not fit
+    pub remaining_bytes: isize,
+    /// Unique accounts locked by the transaction, static or loaded from lookup tables
+    pub account_count: usize,
+    /// Accounts resolved through lookup tables
+    pub lookup_account_count: usize,
+    /// Accounts that need a static key, signers then writable accounts first
+    pub static_accounts: Vec<Pubkey>,
+    pub signer_count: usize,
+    /// Accounts used by the swap instructions only
+    pub swap_account_count: usize,
+    /// Lock limit the transaction was checked against
+    pub account_lock_limit: usize,
+    /// Heuristic `max_accounts` for a follow-up quote so the swap fits with the other
+    /// instructions, assuming new swap accounts cost as much as the current ones
+    pub suggested_max_accounts: u8,
+}
+
+impl TxSizeEstimate {
+    pub fn fits(&self) -> bool {
+        self.remaining_bytes >= 0 && self.account_count <= self.account_lock_limit
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct KeyFlags {
+    signer: bool,
+    writable: bool,
+    invoked: bool,
+}
+
+/// Estimate the transaction compiled from `instructions` with `payer` as fee payer
+///
+/// The accounts not in `other_accounts`, the accounts and programs of the caller
+/// instructions, are counted as swap accounts and drive `suggested_max_accounts`.
+pub(crate) fn estimate(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    other_accounts: &HashSet<Pubkey>,
+    account_lock_limit: usize,
+) -> TxSizeEstimate {
+    // Key order of `CompiledKeys`: payer then first use
+    let mut keys = vec![*payer];
+    let mut flags = HashMap::from([(
+        *payer,
+        KeyFlags {
+            signer: true,
+            writable: true,
+            invoked: false,
+        },
+    )]);
+    for ix in instructions {
+        let program = flags.entry(ix.program_id).or_insert_with(|| {
+            keys.push(ix.program_id);
+            KeyFlags::default()
+        });
+        program.invoked = true;
+        for meta in &ix.accounts {
+            let key = flags.entry(meta.pubkey).or_insert_with(|| {
+                keys.push(meta.pubkey);
+                KeyFlags::default()
+            });
+            key.signer |= meta.is_signer;
+            key.writable |= meta.is_writable;
+        }
+    }
+
+    let mut lookup_candidates = keys
+        .iter()
+        .filter(|key| !flags[key].signer && !flags[key].invoked)
+        .copied()
+        .collect::<HashSet<_>>();
+    let mut lookups_size = 0;
+    let mut lookup_count = 0;
+    for table in lookup_tables {
+        let (mut writable, mut readonly) = (0, 0);
+        for address in &table.addresses {
+            if lookup_candidates.remove(address) {
+                if flags[address].writable {
+                    writable += 1;
+                } else {
+                    readonly += 1;
+                }
+            }
+        }
+        if writable + readonly > 0 {
+            lookup_count += 1;
+            lookups_size +=
+                PUBKEY_LEN + compact_len(writable) + writable + compact_len(readonly) + readonly;
+        }
+    }
+    let resolved_by_lookup = keys
+        .iter()
+        .filter(|key| !flags[key].signer && !flags[key].invoked)
+        .filter(|key| !lookup_candidates.contains(key))
+        .copied()
+        .collect::<HashSet<_>>();
+
+    let mut static_accounts = keys
+        .iter()
+        .filter(|key| !resolved_by_lookup.contains(key))
+        .copied()
+        .collect::<Vec<_>>();
+    // Same order as `v0::Message`: signers then writable accounts first, payer first
+    static_accounts.sort_by_key(|key| {
+        let flags = flags[key];
+        (!flags.signer, !flags.writable, key != payer, *key)
+    });
+    let signer_count = keys.iter().filter(|key| flags[key].signer).count();
+
+    let instructions_size = instructions
+        .iter()
+        .map(|ix| {
+            1 + compact_len(ix.accounts.len())
+                + ix.accounts.len()
+                + compact_len(ix.data.len())
+                + ix.data.len()
+        })
+        .sum::<usize>();
+    let size = compact_len(signer_count)
+        + signer_count * SIGNATURE_LEN
+        // Version prefix and header
+        + 1
+        + 3
+        + compact_len(static_accounts.len())
+        + static_accounts.len() * PUBKEY_LEN
+        // Recent blockhash
+        + PUBKEY_LEN
+        + compact_len(instructions.len())
+        + instructions_size
+        + compact_len(lookup_count)
+        + lookups_size;
+    let remaining_bytes = PACKET_DATA_SIZE as isize - size as isize;
+
+    let swap_only = keys
+        .iter()
+        .filter(|key| *key != payer && !other_accounts.contains(key))
+        .collect::<Vec<_>>();
+    // Static key or lookup index, plus a reference in an instruction
+    let swap_only_bytes = swap_only
+        .iter()
+        .map(|key| {
+            if resolved_by_lookup.contains(key) {
+                2
+            } else {
+                PUBKEY_LEN + 1
+            }
+        })
+        .sum::<usize>();
+    let bytes_per_account = if swap_only.is_empty() {
+        2
+    } else {
+        swap_only_bytes.div_ceil(swap_only.len())
+    };
+    let by_size = (swap_only_bytes as isize + remaining_bytes).max(0) as usize / bytes_per_account;
+    let by_locks = account_lock_limit.saturating_sub(keys.len() - swap_only.len());
+    let suggested_max_accounts = by_size.min(by_locks).min(u8::MAX.into()) as u8;
+
+    TxSizeEstimate {
+        size,
+        remaining_bytes,
+        account_count: keys.len(),
+        lookup_account_count: resolved_by_lookup.len(),
+        static_accounts,
+        signer_count,
+        swap_account_count: swap_only.len(),
+        account_lock_limit,
+        suggested_max_accounts,
+    }
+}
+
+/// Fails with `Error::TransactionTooLarge` if the serialized transaction does not fit in a packet
+pub(crate) fn check_size(tx: &VersionedTransaction) -> Result<()> {
+    let size = bincode::serialized_size(tx)? as usize;
+    if size > PACKET_DATA_SIZE {
+        return Err(Error::TransactionTooLarge {
+            size,
+            over_limit: size - PACKET_DATA_SIZE,
+        });
+    }
+    Ok(())
+}
+
+/// Length of a `short_vec` length prefix
+fn compact_len(len: usize) -> usize {
+    match len {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    }
+}
@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use itertools::Itertools;
 use solana_address_lookup_table_program::state::AddressLookupTable;
 use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_request::MAX_MULTIPLE_ACCOUNTS;
 use solana_program::hash::Hash;
 use solana_program::instruction::AccountMeta;
 use solana_program::message::v0::{self, LoadedAddresses, MessageAddressTableLookup};
@@ -11,30 +12,52 @@ use solana_program::message::{
 };
 use solana_program::pubkey::Pubkey;
 use solana_sdk::account::Account;
+use solana_sdk::clock::Slot;
 use solana_sdk::message::SanitizedMessage;
 use solana_sdk::signer::Signer;
+use solana_sdk::slot_hashes::MAX_ENTRIES;
 use solana_sdk::{
-    address_lookup_table_account::AddressLookupTableAccount,
-    instruction::{Instruction, InstructionError},
+    address_lookup_table_account::AddressLookupTableAccount, instruction::Instruction,
     transaction::VersionedTransaction,
 };
 use std::borrow::Cow;
 use std::collections::HashMap;
 
 use crate::errors::{Error, Result};
+use crate::models::swap_instructions_post_200_response::ApiInstruction;
+use crate::models::SwapInstructionsPost200Response;
+use crate::tx_size;
 
+/// Source of accounts, shared by reference across the async calls of the client
 #[async_trait]
-pub trait AsyncAccountFetcher {
+pub trait AsyncAccountFetcher: Sync {
     async fn fech_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>>;
     async fn get_latest_blockhash(&self) -> Result<Hash>;
+
+    /// Fetch accounts at least as recent as `min_context_slot`, typically the context slot of
+    /// a quote, fetchers without a cache always return recent accounts
+    async fn fech_accounts_at_slot(
+        &self,
+        pubkeys: &[Pubkey],
+        _min_context_slot: Slot,
+    ) -> Result<Vec<Option<Account>>> {
+        self.fech_accounts(pubkeys).await
+    }
+
+    /// Fetch accounts bypassing any cache, e.g. a lookup table missing referenced addresses
+    async fn refetch_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        self.fech_accounts(pubkeys).await
+    }
 }
 
 #[async_trait]
 impl AsyncAccountFetcher for RpcClient {
     async fn fech_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
-        self.get_multiple_accounts(pubkeys)
-            .await
-            .map_err(Into::into)
+        let mut accounts = Vec::with_capacity(pubkeys.len());
+        for batch in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            accounts.extend(self.get_multiple_accounts(batch).await?);
+        }
+        Ok(accounts)
     }
 
     async fn get_latest_blockhash(&self) -> Result<Hash> {
@@ -59,9 +82,9 @@ pub fn get_lookup_tables_pubkeys(tx: &VersionedTransaction) -> Option<Vec<&Pubke
 ///
 /// - `tx`: The transaction to decompile
 /// - `address_loader`: Address loader can be built from pre-loaded lookup tables referenced
-///     by the transaction. Good option here is a `BasicAddressLoader`
-///     Mandatory if the transaction reference a lookup table.
-///     None for legacy tx. The lookup tables are expected
+///   by the transaction. Good option here is a `BasicAddressLoader`
+///   Mandatory if the transaction reference a lookup table.
+///   None for legacy tx. The lookup tables are expected
 pub fn decompile_transaction_instructions(
     tx: VersionedTransaction,
     address_loader: Option<impl AddressLoader>,
@@ -72,33 +95,40 @@ pub fn decompile_transaction_instructions(
     } else {
         SanitizedMessage::try_new(sanitized_versioned_msg, SimpleAddressLoader::Disabled)
     }?;
-    let mut instructions = Vec::with_capacity(sanitized_msg.instructions().len());
     let account_keys = sanitized_msg.account_keys();
-    instructions.extend(sanitized_msg.instructions().iter().map(|compiled_ix| {
-        Instruction::new_with_bytes(
-            *account_keys
+    sanitized_msg
+        .instructions()
+        .iter()
+        .enumerate()
+        .map(|(instruction_index, compiled_ix)| {
+            let missing_account = |account_index: u8| Error::DecompileMissingAccount {
+                instruction_index,
+                account_index,
+            };
+            let program_id = *account_keys
                 .get(compiled_ix.program_id_index.into())
-                .ok_or(InstructionError::MissingAccount)
-                .unwrap(),
-            &compiled_ix.data,
-            compiled_ix
+                .ok_or_else(|| missing_account(compiled_ix.program_id_index))?;
+            let accounts = compiled_ix
                 .accounts
                 .iter()
                 .map(|account_index| {
-                    let account_index = *account_index as usize;
+                    let index = usize::from(*account_index);
                     Ok(AccountMeta {
-                        is_signer: sanitized_msg.is_signer(account_index),
-                        is_writable: sanitized_msg.is_writable(account_index),
+                        is_signer: sanitized_msg.is_signer(index),
+                        is_writable: sanitized_msg.is_writable(index),
                         pubkey: *account_keys
-                            .get(account_index)
-                            .ok_or(InstructionError::MissingAccount)?,
+                            .get(index)
+                            .ok_or_else(|| missing_account(*account_index))?,
                     })
                 })
-                .collect::<std::result::Result<Vec<AccountMeta>, InstructionError>>()
-                .unwrap(),
-        )
-    }));
-    Ok(instructions)
+                .collect::<Result<Vec<AccountMeta>>>()?;
+            Ok(Instruction::new_with_bytes(
+                program_id,
+                &compiled_ix.data,
+                accounts,
+            ))
+        })
+        .collect()
 }
 
 #[derive(Debug)]
@@ -111,15 +141,23 @@ pub async fn decompile_transaction_instructions_with_async_fetcher(
     tx: VersionedTransaction,
     accounts_fetcher: &impl AsyncAccountFetcher,
 ) -> Result<DecompiledVersionedTx> {
-    let lookup_tables_pk = tx
-        .message
-        .address_table_lookups()
-        .map(|tables| tables.iter().map(|table| table.account_key).collect_vec());
-    let address_loader = match lookup_tables_pk {
-        Some(ref tables) => Some(
-            BasicAddressLoader::from_accounts_and_async_accounts_fetcher(tables, accounts_fetcher)
-                .await?,
-        ),
+    decompile_transaction_instructions_with_async_fetcher_at_slot(tx, 0, accounts_fetcher).await
+}
+
+/// Same as `decompile_transaction_instructions_with_async_fetcher` with lookup tables at least
+/// as recent as `min_context_slot`, typically the context slot of the quote
+///
+/// Tables missing an address referenced by the transaction are refetched once, tables
+/// deactivated or being deactivated are rejected with `Error::LookupTableDeactivated`.
+pub async fn decompile_transaction_instructions_with_async_fetcher_at_slot(
+    tx: VersionedTransaction,
+    min_context_slot: Slot,
+    accounts_fetcher: &impl AsyncAccountFetcher,
+) -> Result<DecompiledVersionedTx> {
+    let address_loader = match tx.message.address_table_lookups() {
+        Some(lookups) => {
+            Some(load_lookup_tables(lookups, min_context_slot, accounts_fetcher).await?)
+        }
         None => None,
     };
     let address_loader_ref = address_loader.as_ref();
@@ -131,6 +169,141 @@ pub async fn decompile_transaction_instructions_with_async_fetcher(
     })
 }
 
+async fn load_lookup_tables(
+    lookups: &[MessageAddressTableLookup],
+    min_context_slot: Slot,
+    accounts_fetcher: &impl AsyncAccountFetcher,
+) -> Result<BasicAddressLoader<'static>> {
+    let keys = lookups
+        .iter()
+        .map(|lookup| lookup.account_key)
+        .collect_vec();
+    let accounts = accounts_fetcher
+        .fech_accounts_at_slot(&keys, min_context_slot)
+        .await?;
+    let mut loader = BasicAddressLoader::from_fetched_accounts(&keys, accounts)?;
+    // A cached table can miss the addresses added since it was fetched
+    let stale = loader.tables_missing_indexes(lookups);
+    if !stale.is_empty() {
+        let accounts = accounts_fetcher.refetch_accounts(&stale).await?;
+        loader
+            .0
+            .extend(BasicAddressLoader::from_fetched_accounts(&stale, accounts)?.0);
+    }
+    loader.reject_deactivating_tables(min_context_slot)?;
+    Ok(loader)
+}
+
+/// Instructions of a swap as returned by the swap-instructions endpoint, grouped by purpose
+#[derive(Debug, Clone)]
+pub struct SwapInstructionSet {
+    pub compute_budget_instructions: Vec<Instruction>,
+    pub token_ledger_instruction: Option<Instruction>,
+    pub setup_instructions: Vec<Instruction>,
+    pub swap_instruction: Instruction,
+    pub cleanup_instruction: Option<Instruction>,
+    pub other_instructions: Vec<Instruction>,
+    pub lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+impl SwapInstructionSet {
+    /// All the instructions in the order they are expected in the transaction
+    pub fn instructions(&self) -> Vec<Instruction> {
+        self.compute_budget_instructions
+            .iter()
+            .chain(self.token_ledger_instruction.iter())
+            .chain(self.setup_instructions.iter())
+            .chain(std::iter::once(&self.swap_instruction))
+            .chain(self.cleanup_instruction.iter())
+            .chain(self.other_instructions.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+impl From<SwapInstructionSet> for DecompiledVersionedTx {
+    fn from(instruction_set: SwapInstructionSet) -> Self {
+        let instructions = instruction_set.instructions();
+        let lookup_tables = if instruction_set.lookup_tables.is_empty() {
+            None
+        } else {
+            Some(instruction_set.lookup_tables)
+        };
+        DecompiledVersionedTx {
+            lookup_tables,
+            instructions,
+        }
+    }
+}
+
+/// Convert the swap-instructions endpoint response into a `SwapInstructionSet`, fetching the
+/// referenced lookup tables with the provided fetcher
+pub async fn swap_instruction_set_with_async_fetcher(
+    response: SwapInstructionsPost200Response,
+    accounts_fetcher: &impl AsyncAccountFetcher,
+) -> Result<SwapInstructionSet> {
+    swap_instruction_set_with_async_fetcher_at_slot(response, 0, accounts_fetcher).await
+}
+
+/// Same as `swap_instruction_set_with_async_fetcher` with lookup tables at least as recent as
+/// `min_context_slot`, typically the context slot of the quote
+///
+/// Tables deactivated or being deactivated are rejected with `Error::LookupTableDeactivated`.
+pub async fn swap_instruction_set_with_async_fetcher_at_slot(
+    response: SwapInstructionsPost200Response,
+    min_context_slot: Slot,
+    accounts_fetcher: &impl AsyncAccountFetcher,
+) -> Result<SwapInstructionSet> {
+    let SwapInstructionsPost200Response {
+        token_ledger_instruction,
+        compute_budget_instructions,
+        setup_instructions,
+        swap_instruction,
+        cleanup_instruction,
+        other_instructions,
+        address_lookup_table_addresses,
+        prioritization_fee_lamports: _,
+    } = response;
+
+    let convert_all = |instructions: Vec<ApiInstruction>| -> Result<Vec<Instruction>> {
+        instructions
+            .into_iter()
+            .map(|ix| Instruction::try_from(ix).map_err(Into::into))
+            .collect()
+    };
+
+    let lookup_tables = if address_lookup_table_addresses.is_empty() {
+        Vec::new()
+    } else {
+        let accounts = accounts_fetcher
+            .fech_accounts_at_slot(&address_lookup_table_addresses, min_context_slot)
+            .await?;
+        let loader =
+            BasicAddressLoader::from_fetched_accounts(&address_lookup_table_addresses, accounts)?;
+        loader.reject_deactivating_tables(min_context_slot)?;
+        let mut lookup_tables = loader.to_address_lookup_table_accounts();
+        // Keep the order of the response, the loader does not
+        lookup_tables.sort_by_key(|table| {
+            address_lookup_table_addresses
+                .iter()
+                .position(|key| *key == table.key)
+        });
+        lookup_tables
+    };
+
+    Ok(SwapInstructionSet {
+        compute_budget_instructions: convert_all(compute_budget_instructions)?,
+        token_ledger_instruction: token_ledger_instruction
+            .map(Instruction::try_from)
+            .transpose()?,
+        setup_instructions: convert_all(setup_instructions)?,
+        swap_instruction: swap_instruction.try_into()?,
+        cleanup_instruction: cleanup_instruction.map(Instruction::try_from).transpose()?,
+        other_instructions: convert_all(other_instructions)?,
+        lookup_tables,
+    })
+}
+
 // Note: Needed because Solana only implement `AddressLoader` on banks with all the safety checks
 // that we don't need to just decompile a tx.
 /// Simple address loader that can be built easily from the fetched/Deserialized `AddressLookupTable`
@@ -153,20 +326,7 @@ impl<'a> BasicAddressLoader<'a> {
         accounts_pk: &[Pubkey],
         client_closure: impl FnOnce(&[Pubkey]) -> Result<Vec<Option<Account>>>,
     ) -> Result<BasicAddressLoader<'static>> {
-        let accounts = client_closure(accounts_pk)?;
-        let accounts = accounts
-            .into_iter()
-            .map(|op_account| op_account.ok_or(Error::LookupTableAccountNotFound))
-            .collect::<Result<Vec<_>>>()?;
-        let mut res = HashMap::with_capacity(accounts.len());
-        for (key, account) in accounts_pk.iter().zip(accounts.into_iter()) {
-            let AddressLookupTable { meta, addresses } =
-                AddressLookupTable::deserialize(&account.data)?;
-            let addresses = Cow::Owned(addresses.into_owned());
-            let table = AddressLookupTable { meta, addresses };
-            let _ = res.insert(*key, table);
-        }
-        Ok(BasicAddressLoader(res))
+        Self::from_fetched_accounts(accounts_pk, client_closure(accounts_pk)?)
     }
 
     pub async fn from_accounts_and_async_accounts_fetcher(
@@ -174,12 +334,19 @@ impl<'a> BasicAddressLoader<'a> {
         accounts_fetcher: &impl AsyncAccountFetcher,
     ) -> Result<BasicAddressLoader<'static>> {
         let accounts = accounts_fetcher.fech_accounts(accounts_pk).await?;
+        Self::from_fetched_accounts(accounts_pk, accounts)
+    }
+
+    fn from_fetched_accounts(
+        accounts_pk: &[Pubkey],
+        accounts: Vec<Option<Account>>,
+    ) -> Result<BasicAddressLoader<'static>> {
         let accounts = accounts
             .into_iter()
             .map(|op_account| op_account.ok_or(Error::LookupTableAccountNotFound))
             .collect::<Result<Vec<_>>>()?;
         let mut res = HashMap::with_capacity(accounts.len());
-        for (key, account) in accounts_pk.iter().zip(accounts.into_iter()) {
+        for (key, account) in accounts_pk.iter().zip(accounts) {
             let AddressLookupTable { meta, addresses } =
                 AddressLookupTable::deserialize(&account.data)?;
             let addresses = Cow::Owned(addresses.into_owned());
@@ -188,8 +355,111 @@ impl<'a> BasicAddressLoader<'a> {
         }
         Ok(BasicAddressLoader(res))
     }
+
+    /// Tables with an index of `lookups` past their loaded addresses
+    fn tables_missing_indexes(&self, lookups: &[MessageAddressTableLookup]) -> Vec<Pubkey> {
+        lookups
+            .iter()
+            .filter(|lookup| {
+                self.0.get(&lookup.account_key).is_some_and(|table| {
+                    lookup
+                        .writable_indexes
+                        .iter()
+                        .chain(&lookup.readonly_indexes)
+                        .any(|index| usize::from(*index) >= table.addresses.len())
+                })
+            })
+            .map(|lookup| lookup.account_key)
+            .unique()
+            .collect()
+    }
+
+    /// Same as `from_accounts_and_async_accounts_fetcher` but the tables are checked with
+    /// `check_tables`, and fetched at `quote_slot` or later when set
+    ///
+    /// Deactivated tables are rejected, the tables that are deactivating or were extended at or
+    /// after `quote_slot` are returned alongside the loader.
+    pub async fn from_accounts_and_async_accounts_fetcher_at_slot(
+        accounts_pk: &[Pubkey],
+        accounts_fetcher: &impl AsyncAccountFetcher,
+        current_slot: Slot,
+        quote_slot: Option<Slot>,
+    ) -> Result<(BasicAddressLoader<'static>, Vec<UnsafeLookupTable>)> {
+        let accounts = accounts_fetcher
+            .fech_accounts_at_slot(accounts_pk, quote_slot.unwrap_or(0))
+            .await?;
+        let loader = Self::from_fetched_accounts(accounts_pk, accounts)?;
+        let unsafe_tables = loader.check_tables(current_slot, quote_slot)?;
+        Ok((loader, unsafe_tables))
+    }
+}
+
+/// Reason a lookup table is unsafe to use in a transaction built at a given slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LookupTableIssue {
+    /// Still usable until its deactivation slot leaves the slot hashes, then closable
+    Deactivating { deactivation_slot: Slot },
+    /// Addresses from `start_index` were added at `last_extended_slot`, they cannot be used
+    /// in that slot and the quote may not know them
+    ExtendedAfter {
+        last_extended_slot: Slot,
+        start_index: u8,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsafeLookupTable {
+    pub key: Pubkey,
+    pub issue: LookupTableIssue,
 }
+
 impl BasicAddressLoader<'_> {
+    /// Check the loaded tables at `current_slot`, deactivated tables are an error
+    ///
+    /// A table stays usable for `MAX_ENTRIES` slots after its deactivation slot, it is
+    /// considered deactivated past that. Tables extended at or after `quote_slot`, the context
+    /// slot of the quote, are reported as the quote may not know their new addresses.
+    pub fn check_tables(
+        &self,
+        current_slot: Slot,
+        quote_slot: Option<Slot>,
+    ) -> Result<Vec<UnsafeLookupTable>> {
+        let mut unsafe_tables = Vec::new();
+        for (key, table) in self.0.iter().sorted_by_key(|(key, _)| **key) {
+            let meta = &table.meta;
+            if meta.deactivation_slot != Slot::MAX {
+                if current_slot > meta.deactivation_slot.saturating_add(MAX_ENTRIES as Slot) {
+                    return Err(Error::LookupTableDeactivated(*key));
+                }
+                unsafe_tables.push(UnsafeLookupTable {
+                    key: *key,
+                    issue: LookupTableIssue::Deactivating {
+                        deactivation_slot: meta.deactivation_slot,
+                    },
+                });
+            }
+            if quote_slot.is_some_and(|quote_slot| meta.last_extended_slot >= quote_slot) {
+                unsafe_tables.push(UnsafeLookupTable {
+                    key: *key,
+                    issue: LookupTableIssue::ExtendedAfter {
+                        last_extended_slot: meta.last_extended_slot,
+                        start_index: meta.last_extended_slot_start_index,
+                    },
+                });
+            }
+        }
+        Ok(unsafe_tables)
+    }
+
+    /// `check_tables` for the client paths, which cannot return the unsafe tables: a table
+    /// being deactivated is rejected as well since it can be closed before the transaction lands
+    pub(crate) fn reject_deactivating_tables(&self, current_slot: Slot) -> Result<()> {
+        match self.check_tables(current_slot, None)?.first() {
+            Some(deactivating) => Err(Error::LookupTableDeactivated(deactivating.key)),
+            None => Ok(()),
+        }
+    }
+
     pub fn to_address_lookup_table_accounts(&self) -> Vec<AddressLookupTableAccount> {
         self.0
             .iter()
@@ -256,18 +526,15 @@ pub fn create_tx_with_address_table_lookup(
     payer: &impl Signer,
 ) -> Result<VersionedTransaction> {
     let tx = VersionedTransaction::try_new(
-        VersionedMessage::V0(
-            v0::Message::try_compile(
-                &payer.pubkey(),
-                instructions,
-                address_lookup_tables,
-                recent_blockhash,
-            )
-            .map_err(|_| Error::SolanaCompileError)?,
-        ),
+        VersionedMessage::V0(v0::Message::try_compile(
+            &payer.pubkey(),
+            instructions,
+            address_lookup_tables,
+            recent_blockhash,
+        )?),
         &[payer],
-    )
-    .map_err(|_| Error::SolanaCompileError)?;
+    )?;
+    tx_size::check_size(&tx)?;
 
     Ok(tx)
 }
@@ -0,0 +1,288 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common::{active_table_account, MockFetcher};
+use juno::utils::{
+    decompile_transaction_instructions_with_async_fetcher,
+    decompile_transaction_instructions_with_async_fetcher_at_slot,
+};
+use juno::{AsyncAccountFetcher, CachingAccountFetcher, Error};
+use solana_program::address_lookup_table::state::LookupTableMeta;
+use solana_sdk::{
+    account::Account,
+    address_lookup_table_account::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{v0, VersionedMessage},
+    pubkey::Pubkey,
+    transaction::VersionedTransaction,
+};
+
+mod common;
+
+fn table_account(deactivation_slot: u64) -> Account {
+    common::table_account(
+        LookupTableMeta {
+            deactivation_slot,
+            ..LookupTableMeta::default()
+        },
+        &[Pubkey::new_unique(), Pubkey::new_unique()],
+    )
+}
+
+fn plain_account() -> Account {
+    Account {
+        lamports: 42,
+        ..Account::default()
+    }
+}
+
+#[tokio::test]
+async fn requests_are_deduplicated_and_batched() {
+    let pubkeys = (0..250).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let inner = MockFetcher::new(pubkeys[1..].iter().map(|pubkey| (*pubkey, plain_account())));
+    let fetcher = CachingAccountFetcher::new(inner);
+
+    let requested = pubkeys
+        .iter()
+        .chain(&pubkeys[..10])
+        .copied()
+        .collect::<Vec<_>>();
+    let accounts = fetcher.fech_accounts(&requested).await.unwrap();
+
+    assert_eq!(accounts.len(), 260);
+    assert_eq!(accounts[0], None);
+    assert_eq!(accounts[250], None);
+    assert_eq!(accounts[1], Some(plain_account()));
+    assert_eq!(accounts[251], Some(plain_account()));
+    assert_eq!(fetcher.inner().batch_sizes(), vec![50, 100, 100]);
+}
+
+#[tokio::test]
+async fn only_active_lookup_tables_are_served_from_memory() {
+    let (table, deactivated_table, account) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let inner = MockFetcher::new([
+        (table, table_account(u64::MAX)),
+        (deactivated_table, table_account(1_000)),
+        (account, plain_account()),
+    ]);
+    let fetcher = CachingAccountFetcher::new(inner).with_batch_size(2);
+
+    let keys = [table, deactivated_table, account];
+    let first = fetcher.fech_accounts(&keys).await.unwrap();
+    let second = fetcher.fech_accounts(&keys).await.unwrap();
+    assert_eq!(first, second);
+
+    let fetched = fetcher.inner().fetched();
+    assert_eq!(fetched.len(), 5);
+    assert_eq!(fetched.iter().filter(|key| **key == table).count(), 1);
+    assert_eq!((fetcher.cache_hits(), fetcher.cache_misses()), (1, 5));
+
+    fetcher.invalidate(&table);
+    fetcher.fech_accounts(&[table]).await.unwrap();
+    assert_eq!(fetcher.inner().fetched().len(), 6);
+}
+
+#[tokio::test]
+async fn cached_lookup_tables_expire() {
+    let table = Pubkey::new_unique();
+    let inner = MockFetcher::new([(table, table_account(u64::MAX))]);
+    let fetcher =
+        CachingAccountFetcher::new(inner).with_lookup_table_ttl(Duration::from_millis(50));
+
+    fetcher.fech_accounts(&[table]).await.unwrap();
+    fetcher.fech_accounts(&[table]).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    fetcher.fech_accounts(&[table]).await.unwrap();
+
+    assert_eq!(fetcher.inner().fetched().len(), 2);
+}
+
+/// Drops the last account of every batch
+struct ShortBatchFetcher(MockFetcher);
+
+#[async_trait]
+impl AsyncAccountFetcher for ShortBatchFetcher {
+    async fn fech_accounts(&self, pubkeys: &[Pubkey]) -> juno::Result<Vec<Option<Account>>> {
+        let mut accounts = self.0.fech_accounts(pubkeys).await?;
+        accounts.pop();
+        Ok(accounts)
+    }
+
+    async fn get_latest_blockhash(&self) -> juno::Result<Hash> {
+        self.0.get_latest_blockhash().await
+    }
+}
+
+#[tokio::test]
+async fn short_batches_are_rejected_and_not_cached() {
+    let table = Pubkey::new_unique();
+    let fetcher = CachingAccountFetcher::new(ShortBatchFetcher(MockFetcher::new([(
+        table,
+        table_account(u64::MAX),
+    )])));
+
+    let error = fetcher
+        .fech_accounts(&[table, Pubkey::new_unique()])
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(
+            error,
+            Error::AccountCountMismatch {
+                requested: 2,
+                returned: 1
+            }
+        ),
+        "{error:?}"
+    );
+    fetcher.fech_accounts(&[table]).await.unwrap_err();
+    assert_eq!(fetcher.cache_hits(), 0);
+}
+
+#[tokio::test]
+async fn cached_lookup_tables_serve_later_slots() {
+    let table = Pubkey::new_unique();
+    let fetcher = CachingAccountFetcher::new(MockFetcher::new([(table, table_account(u64::MAX))]));
+
+    for slot in [100, 101, 50, 1_000] {
+        fetcher.fech_accounts_at_slot(&[table], slot).await.unwrap();
+    }
+
+    assert_eq!(fetcher.inner().fetched().len(), 1);
+    assert_eq!((fetcher.cache_hits(), fetcher.cache_misses()), (3, 1));
+}
+
+#[tokio::test]
+async fn refetches_bypass_and_refresh_the_cache() {
+    let table = Pubkey::new_unique();
+    let addresses = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let fetcher = CachingAccountFetcher::new(MockFetcher::new([(
+        table,
+        active_table_account(&addresses[..1]),
+    )]));
+    fetcher.fech_accounts(&[table]).await.unwrap();
+
+    fetcher
+        .inner()
+        .set_account(table, active_table_account(&addresses));
+    let refetched = fetcher.refetch_accounts(&[table]).await.unwrap();
+    let cached = fetcher.fech_accounts(&[table]).await.unwrap();
+
+    assert_eq!(refetched, cached);
+    assert_eq!(cached[0], Some(active_table_account(&addresses)));
+    assert_eq!(fetcher.inner().fetched().len(), 2);
+}
+
+/// Transaction with a v0 message loading `addresses[index]` from `table`
+fn transaction_using(table: Pubkey, addresses: &[Pubkey], index: usize) -> VersionedTransaction {
+    let payer = Pubkey::new_unique();
+    let instruction = Instruction::new_with_bytes(
+        Pubkey::new_unique(),
+        &[],
+        vec![AccountMeta::new(addresses[index], false)],
+    );
+    let lookup_table = AddressLookupTableAccount {
+        key: table,
+        addresses: addresses.to_vec(),
+    };
+    let message =
+        v0::Message::try_compile(&payer, &[instruction], &[lookup_table], Hash::default()).unwrap();
+    VersionedTransaction {
+        signatures: vec![],
+        message: VersionedMessage::V0(message),
+    }
+}
+
+#[tokio::test]
+async fn stale_cached_tables_are_refetched_once_when_decompiling() {
+    let table = Pubkey::new_unique();
+    let addresses = (0..4).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let fetcher = CachingAccountFetcher::new(MockFetcher::new([(
+        table,
+        active_table_account(&addresses[..2]),
+    )]));
+    fetcher.fech_accounts(&[table]).await.unwrap();
+    fetcher
+        .inner()
+        .set_account(table, active_table_account(&addresses));
+
+    let decompiled = decompile_transaction_instructions_with_async_fetcher(
+        transaction_using(table, &addresses, 3),
+        &fetcher,
+    )
+    .await
+    .unwrap();
+    assert_eq!(decompiled.instructions[0].accounts[0].pubkey, addresses[3]);
+    assert_eq!(fetcher.inner().fetched().len(), 2);
+
+    let unknown = [addresses.clone(), vec![Pubkey::new_unique()]].concat();
+    let result = decompile_transaction_instructions_with_async_fetcher(
+        transaction_using(table, &unknown, 4),
+        &fetcher,
+    )
+    .await;
+    assert!(result.is_err());
+    assert_eq!(fetcher.inner().fetched().len(), 3);
+}
+
+#[tokio::test]
+async fn quotes_at_later_slots_share_cached_tables() {
+    let table = Pubkey::new_unique();
+    let addresses = (0..4).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let fetcher = CachingAccountFetcher::new(MockFetcher::new([(
+        table,
+        active_table_account(&addresses),
+    )]));
+
+    for (context_slot, index) in [(1_000, 1), (1_050, 3)] {
+        let decompiled = decompile_transaction_instructions_with_async_fetcher_at_slot(
+            transaction_using(table, &addresses, index),
+            context_slot,
+            &fetcher,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            decompiled.instructions[0].accounts[0].pubkey,
+            addresses[index]
+        );
+    }
+
+    assert_eq!(fetcher.inner().fetched(), [table]);
+}
+
+#[tokio::test]
+async fn deactivating_tables_are_rejected_when_decompiling() {
+    let table = Pubkey::new_unique();
+    let addresses = [Pubkey::new_unique()];
+    let fetcher = MockFetcher::new([(table, table_account(1_000))]);
+    fetcher.set_account(
+        table,
+        common::table_account(
+            LookupTableMeta {
+                deactivation_slot: 1_000,
+                ..LookupTableMeta::default()
+            },
+            &addresses,
+        ),
+    );
+
+    let error = decompile_transaction_instructions_with_async_fetcher_at_slot(
+        transaction_using(table, &addresses, 0),
+        1_010,
+        &fetcher,
+    )
+    .await
+    .unwrap_err();
+
+    assert!(
+        matches!(error, Error::LookupTableDeactivated(key) if key == table),
+        "{error:?}"
+    );
+}
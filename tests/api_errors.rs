@@ -0,0 +1,82 @@
+use juno::{Error, JupiterApiError, JupiterErrorCode};
+use reqwest::StatusCode;
+
+fn to_error(status: StatusCode, content: &str) -> Error {
+    JupiterApiError::from_response(status, content).into()
+}
+
+#[test]
+fn error_body_is_parsed_into_code_and_message() {
+    let api_error = JupiterApiError::from_response(
+        StatusCode::BAD_REQUEST,
+        r#"{"error":"Token not tradable","errorCode":"TOKEN_NOT_TRADABLE"}"#,
+    );
+
+    assert_eq!(api_error.code, Some(JupiterErrorCode::TokenNotTradable));
+    assert_eq!(api_error.message, "Token not tradable");
+    assert_eq!(
+        api_error.to_string(),
+        "status 400 Bad Request (TOKEN_NOT_TRADABLE): Token not tradable"
+    );
+}
+
+#[test]
+fn unknown_codes_and_bodies_are_kept() {
+    let api_error = JupiterApiError::from_response(
+        StatusCode::BAD_REQUEST,
+        r#"{"message":"Amount too small","errorCode":"AMOUNT_TOO_SMALL"}"#,
+    );
+    assert_eq!(
+        api_error.code,
+        Some(JupiterErrorCode::Other("AMOUNT_TOO_SMALL".to_owned()))
+    );
+    assert_eq!(api_error.message, "Amount too small");
+
+    let api_error = JupiterApiError::from_response(StatusCode::BAD_GATEWAY, "<html>502</html>");
+    assert_eq!(api_error.code, None);
+    assert_eq!(api_error.message, "<html>502</html>");
+}
+
+#[test]
+fn no_route_codes_map_to_no_valid_route() {
+    let error = to_error(
+        StatusCode::BAD_REQUEST,
+        r#"{"error":"Could not find any route","errorCode":"COULD_NOT_FIND_ANY_ROUTE"}"#,
+    );
+
+    assert!(matches!(error, Error::NoValidRoute), "{error:?}");
+}
+
+#[test]
+fn token_not_tradable_is_an_api_error() {
+    let error = to_error(
+        StatusCode::BAD_REQUEST,
+        r#"{"error":"Token not tradable","errorCode":"TOKEN_NOT_TRADABLE"}"#,
+    );
+
+    assert!(
+        matches!(
+            &error,
+            Error::JupiterApi(JupiterApiError {
+                code: Some(JupiterErrorCode::TokenNotTradable),
+                ..
+            })
+        ),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn server_and_rate_limit_statuses_are_told_apart() {
+    let error = to_error(
+        StatusCode::SERVICE_UNAVAILABLE,
+        r#"{"error":"Unavailable"}"#,
+    );
+    assert!(
+        matches!(&error, Error::JupiterServer(api_error) if api_error.status == StatusCode::SERVICE_UNAVAILABLE),
+        "{error:?}"
+    );
+
+    let error = to_error(StatusCode::TOO_MANY_REQUESTS, "Too many requests");
+    assert!(matches!(error, Error::RateLimited(_)), "{error:?}");
+}
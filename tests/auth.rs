@@ -0,0 +1,77 @@
+use juno::{Configuration, JupiterClient, QuoteRequest, RetryPolicy};
+use reqwest::header::{HeaderName, HeaderValue};
+use solana_sdk::pubkey::Pubkey;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const QUOTE: &str = include_str!("fixtures/quote.json");
+const API_KEY: &str = "super-secret-api-key";
+const TOKEN: &str = "super-secret-token";
+
+#[tokio::test]
+async fn credentials_and_extra_headers_are_sent() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .and(header("x-api-key", API_KEY))
+        .and(header("authorization", format!("Bearer {TOKEN}").as_str()))
+        .and(header("x-team", "liquidations"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(QUOTE))
+        .mount(&server)
+        .await;
+
+    let client = JupiterClient::with_configuration(
+        Configuration::new(server.uri())
+            .with_retry_policy(RetryPolicy::none())
+            .with_api_key(API_KEY)
+            .with_bearer_access_token(TOKEN)
+            .with_header(
+                HeaderName::from_static("x-team"),
+                HeaderValue::from_static("liquidations"),
+            ),
+    );
+
+    client
+        .get_quote(&QuoteRequest::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1,
+        ))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn credentials_never_show_in_debug_or_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(ResponseTemplate::new(401).set_body_string(r#"{"error":"unauthorized"}"#))
+        .mount(&server)
+        .await;
+
+    let mut configuration = Configuration::new(server.uri())
+        .with_retry_policy(RetryPolicy::none())
+        .with_api_key(API_KEY)
+        .with_bearer_access_token(TOKEN);
+    configuration.basic_auth = Some(("user".to_owned(), Some(TOKEN.to_owned())));
+    configuration.oauth_access_token = Some(TOKEN.to_owned());
+    let client = JupiterClient::with_configuration(configuration);
+
+    let debug = format!("{client:?}");
+    assert!(!debug.contains(API_KEY), "{debug}");
+    assert!(!debug.contains(TOKEN), "{debug}");
+
+    let err = client
+        .get_quote(&QuoteRequest::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1,
+        ))
+        .await
+        .unwrap_err();
+    for output in [format!("{err}"), format!("{err:?}")] {
+        assert!(!output.contains(API_KEY), "{output}");
+        assert!(!output.contains(TOKEN), "{output}");
+    }
+}
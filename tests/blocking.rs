@@ -0,0 +1,204 @@
+#![cfg(feature = "blocking")]
+
+use common::{active_table_account, mint_account, table_account, MockFetcher};
+use juno::blocking::JupiterClient;
+use juno::mint::TOKEN_PROGRAM_ID;
+use juno::reexports::Decimal;
+use juno::{Configuration, Error, QuoteRequest, RetryPolicy, SwapOptions, SwapRoute};
+use serde_json::{json, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_program::address_lookup_table::state::LookupTableMeta;
+use solana_sdk::account::Account;
+use solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::{v0, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+mod common;
+
+const QUOTE: &str = include_str!("fixtures/quote.json");
+
+/// The mock server runs on its own thread, the runtime is only needed to set it up
+fn mock_server(amount: &str) -> (tokio::runtime::Runtime, MockServer) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(async {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/quote"))
+            .and(query_param("amount", amount))
+            .respond_with(ResponseTemplate::new(200).set_body_string(QUOTE))
+            .expect(1)
+            .mount(&server)
+            .await;
+        server
+    });
+    (runtime, server)
+}
+
+#[test]
+fn quotes_are_fetched_synchronously() {
+    let (_runtime, server) = mock_server("1000");
+    let client = JupiterClient::with_configuration(
+        Configuration::new(server.uri()).with_retry_policy(RetryPolicy::none()),
+    );
+
+    let quote = client
+        .get_quote(&QuoteRequest::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1000,
+        ))
+        .unwrap();
+    assert!(quote.out_amount > 0);
+}
+
+#[test]
+fn sync_fetchers_resolve_mint_decimals() {
+    let (_runtime, server) = mock_server("2500000");
+    let (input_mint, output_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let fetcher = MockFetcher::new([
+        (input_mint, mint_account(TOKEN_PROGRAM_ID, 6, 0)),
+        (output_mint, mint_account(TOKEN_PROGRAM_ID, 9, 0)),
+    ]);
+    let client = JupiterClient::with_configuration(
+        Configuration::new(server.uri()).with_retry_policy(RetryPolicy::none()),
+    );
+
+    client
+        .get_quote_ui(&input_mint, &output_mint, Decimal::new(25, 1), &fetcher)
+        .unwrap();
+    assert_eq!(
+        client.get_mint_decimals(&[output_mint], &fetcher).unwrap(),
+        vec![9]
+    );
+}
+
+/// JSON-RPC node answering `getMultipleAccounts` with `account` for every key
+struct RpcResponder(Account);
+
+impl Respond for RpcResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let request = serde_json::from_slice::<Value>(&request.body).unwrap();
+        let result = match request["method"].as_str().unwrap() {
+            "getVersion" => json!({ "solana-core": "1.18.26" }),
+            "getMultipleAccounts" => {
+                let account = json!({
+                    "lamports": self.0.lamports,
+                    "data": [base64::encode(&self.0.data), "base64"],
+                    "owner": self.0.owner.to_string(),
+                    "executable": false,
+                    "rentEpoch": 0,
+                });
+                let keys = request["params"][0].as_array().unwrap();
+                json!({
+                    "context": { "slot": 245_123_456 },
+                    "value": vec![account; keys.len()],
+                })
+            }
+            method => panic!("unexpected RPC method {method}"),
+        };
+        ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": result,
+        }))
+    }
+}
+
+/// Serves a swap transaction loading `address` from `table`, and `table_account` on the RPC
+/// endpoint of the same server
+fn swap_server(
+    table: Pubkey,
+    address: Pubkey,
+    table_account: Account,
+) -> (tokio::runtime::Runtime, MockServer) {
+    let instruction = Instruction::new_with_bytes(
+        Pubkey::new_unique(),
+        &[],
+        vec![AccountMeta::new(address, false)],
+    );
+    let lookup_table = AddressLookupTableAccount {
+        key: table,
+        addresses: vec![address],
+    };
+    let message = v0::Message::try_compile(
+        &Pubkey::new_unique(),
+        &[instruction],
+        &[lookup_table],
+        Hash::default(),
+    )
+    .unwrap();
+    let transaction = VersionedTransaction {
+        signatures: vec![],
+        message: VersionedMessage::V0(message),
+    };
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(async {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/swap"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "swapTransaction": base64::encode(bincode::serialize(&transaction).unwrap()),
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/"))
+            .respond_with(RpcResponder(table_account))
+            .mount(&server)
+            .await;
+        server
+    });
+    (runtime, server)
+}
+
+fn swap_instructions(server: &MockServer) -> juno::Result<juno::DecompiledVersionedTx> {
+    let client = JupiterClient::with_configuration(
+        Configuration::new(server.uri()).with_retry_policy(RetryPolicy::none()),
+    );
+    let route: SwapRoute = serde_json::from_str(QUOTE).unwrap();
+    client.get_swap_instructions(
+        route,
+        Pubkey::new_unique(),
+        &SwapOptions::default(),
+        &RpcClient::new(server.uri()),
+    )
+}
+
+#[test]
+fn rpc_clients_fetch_lookup_tables() {
+    let (table, address) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (_runtime, server) = swap_server(table, address, active_table_account(&[address]));
+
+    let decompiled = swap_instructions(&server).unwrap();
+
+    assert_eq!(decompiled.instructions[0].accounts[0].pubkey, address);
+    let tables = decompiled.lookup_tables.unwrap();
+    assert_eq!(
+        (tables[0].key, tables[0].addresses.as_slice()),
+        (table, &[address][..])
+    );
+}
+
+#[test]
+fn swap_instructions_reject_tables_deactivated_before_the_quote() {
+    let (table, address) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let meta = LookupTableMeta {
+        // Before the quote context slot
+        deactivation_slot: 245_000_000,
+        ..LookupTableMeta::default()
+    };
+    let (_runtime, server) = swap_server(table, address, table_account(meta, &[address]));
+
+    let error = swap_instructions(&server).unwrap_err();
+
+    assert!(
+        matches!(error, Error::LookupTableDeactivated(key) if key == table),
+        "{error:?}"
+    );
+}
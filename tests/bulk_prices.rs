@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use juno::reexports::Decimal;
+use juno::{Configuration, JupiterClient, RetryPolicy};
+use solana_sdk::pubkey::Pubkey;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// Prices every requested id but the ones in `unknown`, tracking the concurrent requests
+struct PriceResponder {
+    vs_token: Pubkey,
+    unknown: HashSet<Pubkey>,
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+}
+
+impl Respond for PriceResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        // Responders are called synchronously, keep the slot busy to observe overlapping calls
+        std::thread::sleep(Duration::from_millis(20));
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        let ids = request
+            .url
+            .query_pairs()
+            .find(|(key, _)| key == "ids")
+            .map(|(_, ids)| ids.into_owned())
+            .unwrap();
+        let data = ids
+            .split(',')
+            .map(|id| id.parse::<Pubkey>().unwrap())
+            .filter(|id| !self.unknown.contains(id))
+            .map(|id| {
+                format!(
+                    r#""{id}":{{"id":"{id}","vsToken":"{}","price":1.5}}"#,
+                    self.vs_token
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        ResponseTemplate::new(200).set_body_string(format!(r#"{{"data":{{{data}}}}}"#))
+    }
+}
+
+#[tokio::test]
+async fn mints_are_chunked_merged_and_missing_ones_reported() {
+    let server = MockServer::start().await;
+    let vs_token = Pubkey::new_unique();
+    let mints = (0..25).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let unknown = HashSet::from([mints[3], mints[17]]);
+    let max_in_flight = Arc::new(AtomicUsize::new(0));
+    Mock::given(method("GET"))
+        .and(path("/v4/price"))
+        .respond_with(PriceResponder {
+            vs_token,
+            unknown: unknown.clone(),
+            in_flight: Arc::default(),
+            max_in_flight: max_in_flight.clone(),
+        })
+        .expect(5)
+        .mount(&server)
+        .await;
+    let client = JupiterClient::with_configuration(
+        Configuration::new(server.uri())
+            .with_retry_policy(RetryPolicy::none())
+            .with_price_chunking(6, 2),
+    );
+
+    // Duplicates are requested once
+    let requested = mints.iter().chain(&mints[..4]).copied().collect::<Vec<_>>();
+    let prices = client
+        .get_prices_bulk(&requested, &vs_token, Decimal::ONE)
+        .await
+        .unwrap();
+
+    assert_eq!(prices.prices.len(), 23);
+    assert_eq!(prices.missing, vec![mints[3], mints[17]]);
+    assert!(!prices.is_complete());
+    assert_eq!(prices.get(&mints[0]).unwrap().price, Decimal::new(15, 1));
+    assert!(max_in_flight.load(Ordering::SeqCst) <= 2);
+}
+
+#[tokio::test]
+async fn empty_input_sends_no_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+    let client = JupiterClient::new(server.uri());
+
+    let prices = client
+        .get_prices_bulk(&[], &Pubkey::new_unique(), Decimal::ONE)
+        .await
+        .unwrap();
+    assert!(prices.prices.is_empty() && prices.is_complete());
+    assert!(client
+        .get_prices(&[], &Pubkey::new_unique(), Decimal::ONE)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn a_failing_chunk_fails_the_call() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(400).set_body_string(r#"{"error":"bad ids"}"#))
+        .mount(&server)
+        .await;
+    let client = JupiterClient::with_configuration(
+        Configuration::new(server.uri()).with_retry_policy(RetryPolicy::none()),
+    );
+
+    let mints = (0..3).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    assert!(client
+        .get_prices_bulk(&mints, &Pubkey::new_unique(), Decimal::ONE)
+        .await
+        .is_err());
+}
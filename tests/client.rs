@@ -0,0 +1,46 @@
+use juno::{Configuration, JupiterClient, QuoteRequest, RetryPolicy};
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const QUOTE: &str = include_str!("fixtures/quote.json");
+
+/// Server quoting `in_amount` for any request
+async fn server(in_amount: u64) -> MockServer {
+    let server = MockServer::start().await;
+    let mut quote = serde_json::from_str::<Value>(QUOTE).unwrap();
+    quote["inAmount"] = in_amount.to_string().into();
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(quote))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn received_requests(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap_or_default().len()
+}
+
+fn client(server: &MockServer) -> JupiterClient {
+    JupiterClient::with_configuration(
+        Configuration::new(server.uri()).with_retry_policy(RetryPolicy::none()),
+    )
+}
+
+#[tokio::test]
+async fn clients_on_different_hosts_only_reach_their_own() {
+    let first_server = server(1).await;
+    let second_server = server(2).await;
+    let (first, second) = (client(&first_server), client(&second_server));
+    let request = QuoteRequest::new(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+
+    for _ in 0..2 {
+        assert_eq!(first.get_quote(&request).await.unwrap().in_amount, 1);
+    }
+    assert_eq!(second.get_quote(&request).await.unwrap().in_amount, 2);
+
+    assert_eq!(received_requests(&first_server).await, 2);
+    assert_eq!(received_requests(&second_server).await, 1);
+}
@@ -0,0 +1,111 @@
+//! Fixtures shared by the integration tests, each test crate uses a subset of them
+#![allow(dead_code)]
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use juno::AsyncAccountFetcher;
+use solana_program::address_lookup_table::state::{AddressLookupTable, LookupTableMeta};
+use solana_sdk::{account::Account, hash::Hash, pubkey::Pubkey};
+
+/// Serves the given accounts and records every batch it is asked for
+#[derive(Default)]
+pub struct MockFetcher {
+    accounts: Mutex<HashMap<Pubkey, Account>>,
+    batches: Mutex<Vec<Vec<Pubkey>>>,
+}
+
+impl MockFetcher {
+    pub fn new(accounts: impl IntoIterator<Item = (Pubkey, Account)>) -> Self {
+        Self {
+            accounts: Mutex::new(accounts.into_iter().collect()),
+            ..Self::default()
+        }
+    }
+
+    /// Replace an account, e.g. a lookup table extended on-chain
+    pub fn set_account(&self, pubkey: Pubkey, account: Account) {
+        self.accounts.lock().unwrap().insert(pubkey, account);
+    }
+
+    pub fn batch_sizes(&self) -> Vec<usize> {
+        let mut sizes = self
+            .batches
+            .lock()
+            .unwrap()
+            .iter()
+            .map(Vec::len)
+            .collect::<Vec<_>>();
+        sizes.sort_unstable();
+        sizes
+    }
+
+    /// Every key fetched so far, in request order
+    pub fn fetched(&self) -> Vec<Pubkey> {
+        self.batches.lock().unwrap().concat()
+    }
+
+    fn get(&self, pubkeys: &[Pubkey]) -> Vec<Option<Account>> {
+        assert!(pubkeys.len() <= 100, "RPC limit exceeded");
+        self.batches.lock().unwrap().push(pubkeys.to_vec());
+        let accounts = self.accounts.lock().unwrap();
+        pubkeys
+            .iter()
+            .map(|pubkey| accounts.get(pubkey).cloned())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl AsyncAccountFetcher for MockFetcher {
+    async fn fech_accounts(&self, pubkeys: &[Pubkey]) -> juno::Result<Vec<Option<Account>>> {
+        Ok(self.get(pubkeys))
+    }
+
+    async fn get_latest_blockhash(&self) -> juno::Result<Hash> {
+        Ok(Hash::default())
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl juno::blocking::AccountFetcher for MockFetcher {
+    fn fech_accounts(&self, pubkeys: &[Pubkey]) -> juno::Result<Vec<Option<Account>>> {
+        Ok(self.get(pubkeys))
+    }
+
+    fn get_latest_blockhash(&self) -> juno::Result<Hash> {
+        Ok(Hash::default())
+    }
+}
+
+/// Lookup table account owned by the lookup table program
+pub fn table_account(meta: LookupTableMeta, addresses: &[Pubkey]) -> Account {
+    let table = AddressLookupTable {
+        meta,
+        addresses: Cow::Borrowed(addresses),
+    };
+    Account {
+        owner: solana_program::address_lookup_table::program::id(),
+        data: table.serialize_for_tests().unwrap(),
+        ..Account::default()
+    }
+}
+
+/// Active lookup table account holding `addresses`
+pub fn active_table_account(addresses: &[Pubkey]) -> Account {
+    table_account(LookupTableMeta::default(), addresses)
+}
+
+/// Initialized mint account, `extension_len` bytes of Token-2022 extensions after the base mint
+pub fn mint_account(owner: Pubkey, decimals: u8, extension_len: usize) -> Account {
+    let mut data = vec![0; 82 + extension_len];
+    data[44] = decimals;
+    data[45] = 1;
+    Account {
+        owner,
+        data,
+        ..Account::default()
+    }
+}
@@ -0,0 +1,88 @@
+use juno::utils::create_tx_with_address_table_lookup;
+use juno::{Error, TxComposer};
+use solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::CompileError;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::signer::SignerError;
+
+fn instruction(signer: &Pubkey, accounts: &[Pubkey], data_len: usize) -> Instruction {
+    let mut metas = vec![AccountMeta::new(*signer, true)];
+    metas.extend(accounts.iter().map(|key| AccountMeta::new(*key, false)));
+    Instruction::new_with_bytes(Pubkey::new_unique(), &vec![0; data_len], metas)
+}
+
+#[test]
+fn oversized_transactions_report_the_bytes_over_the_limit() {
+    let payer = Keypair::new();
+    let ix = instruction(&payer.pubkey(), &[], PACKET_DATA_SIZE);
+
+    let error =
+        create_tx_with_address_table_lookup(&[ix], &[], Hash::new_unique(), &payer).unwrap_err();
+
+    let Error::TransactionTooLarge { size, over_limit } = error else {
+        panic!("unexpected error: {error:?}");
+    };
+    assert_eq!(size - over_limit, PACKET_DATA_SIZE);
+    assert!(over_limit > 0);
+    assert!(error.to_string().contains(&format!("{over_limit} over")));
+}
+
+#[test]
+fn too_many_accounts_overflow_the_account_index() {
+    let payer = Keypair::new();
+    let addresses = (0..300).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let table = AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: addresses[..250].to_vec(),
+    };
+    let ix = instruction(&payer.pubkey(), &addresses, 0);
+
+    let error = create_tx_with_address_table_lookup(&[ix], &[table], Hash::new_unique(), &payer)
+        .unwrap_err();
+
+    assert!(
+        matches!(
+            error,
+            Error::SolanaCompile(CompileError::AccountIndexOverflow)
+        ),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn missing_signers_are_reported() {
+    let payer = Keypair::new();
+    let ix = instruction(&Pubkey::new_unique(), &[], 0);
+
+    let error =
+        create_tx_with_address_table_lookup(&[ix], &[], Hash::new_unique(), &payer).unwrap_err();
+
+    assert!(
+        matches!(error, Error::SolanaSigner(SignerError::NotEnoughSigners)),
+        "{error:?}"
+    );
+    assert!(std::error::Error::source(&error).is_some());
+}
+
+#[test]
+fn composed_transactions_are_checked_for_size() {
+    let payer = Keypair::new();
+    let composer = TxComposer::new(payer.pubkey()).pre_instructions([instruction(
+        &payer.pubkey(),
+        &[],
+        PACKET_DATA_SIZE,
+    )]);
+
+    assert!(matches!(
+        composer.build_unsigned(Hash::new_unique()),
+        Err(Error::TransactionTooLarge { .. })
+    ));
+    assert!(matches!(
+        composer.build_signed(Hash::new_unique(), &[&payer]),
+        Err(Error::TransactionTooLarge { .. })
+    ));
+}
@@ -0,0 +1,205 @@
+use common::active_table_account;
+use juno::utils::{decompile_transaction_instructions, BasicAddressLoader};
+use proptest::prelude::*;
+use solana_sdk::{
+    account::Account,
+    address_lookup_table_account::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::{AccountMeta, CompiledInstruction, Instruction},
+    message::{
+        legacy,
+        v0::{self, MessageAddressTableLookup},
+        MessageHeader, VersionedMessage,
+    },
+    pubkey::Pubkey,
+    transaction::VersionedTransaction,
+};
+
+mod common;
+
+const TABLE_COUNT: usize = 2;
+
+fn pubkey() -> impl Strategy<Value = Pubkey> {
+    any::<[u8; 32]>().prop_map(Pubkey::new_from_array)
+}
+
+fn header() -> impl Strategy<Value = MessageHeader> {
+    (0..4u8, 0..4u8, 0..4u8).prop_map(
+        |(
+            num_required_signatures,
+            num_readonly_signed_accounts,
+            num_readonly_unsigned_accounts,
+        )| {
+            MessageHeader {
+                num_required_signatures,
+                num_readonly_signed_accounts,
+                num_readonly_unsigned_accounts,
+            }
+        },
+    )
+}
+
+fn compiled_instruction() -> impl Strategy<Value = CompiledInstruction> {
+    (
+        0..16u8,
+        prop::collection::vec(0..16u8, 0..6),
+        prop::collection::vec(any::<u8>(), 0..8),
+    )
+        .prop_map(|(program_id_index, accounts, data)| CompiledInstruction {
+            program_id_index,
+            accounts,
+            data,
+        })
+}
+
+fn lookup_tables() -> impl Strategy<Value = Vec<(Pubkey, Vec<Pubkey>)>> {
+    prop::collection::vec(
+        (pubkey(), prop::collection::vec(pubkey(), 0..6)),
+        TABLE_COUNT,
+    )
+}
+
+fn address_table_lookup(
+    tables: Vec<(Pubkey, Vec<Pubkey>)>,
+) -> impl Strategy<Value = MessageAddressTableLookup> {
+    (
+        prop_oneof![
+            (0..TABLE_COUNT).prop_map(move |index| tables[index].0),
+            pubkey(),
+        ],
+        prop::collection::vec(0..8u8, 0..4),
+        prop::collection::vec(0..8u8, 0..4),
+    )
+        .prop_map(|(account_key, writable_indexes, readonly_indexes)| {
+            MessageAddressTableLookup {
+                account_key,
+                writable_indexes,
+                readonly_indexes,
+            }
+        })
+}
+
+fn versioned_message(
+    tables: Vec<(Pubkey, Vec<Pubkey>)>,
+) -> impl Strategy<Value = VersionedMessage> {
+    let account_keys = || prop::collection::vec(pubkey(), 0..8);
+    let instructions = || prop::collection::vec(compiled_instruction(), 0..4);
+    prop_oneof![
+        (header(), account_keys(), instructions()).prop_map(
+            |(header, account_keys, instructions)| {
+                VersionedMessage::Legacy(legacy::Message {
+                    header,
+                    account_keys,
+                    recent_blockhash: Hash::default(),
+                    instructions,
+                })
+            }
+        ),
+        (
+            header(),
+            account_keys(),
+            instructions(),
+            prop::collection::vec(address_table_lookup(tables), 0..3),
+        )
+            .prop_map(
+                |(header, account_keys, instructions, address_table_lookups)| {
+                    VersionedMessage::V0(v0::Message {
+                        header,
+                        account_keys,
+                        recent_blockhash: Hash::default(),
+                        instructions,
+                        address_table_lookups,
+                    })
+                }
+            ),
+    ]
+}
+
+fn arbitrary_transaction(
+) -> impl Strategy<Value = (Vec<(Pubkey, Vec<Pubkey>)>, VersionedTransaction)> {
+    lookup_tables().prop_flat_map(|tables| {
+        versioned_message(tables.clone()).prop_map(move |message| {
+            (
+                tables.clone(),
+                VersionedTransaction {
+                    signatures: vec![],
+                    message,
+                },
+            )
+        })
+    })
+}
+
+proptest! {
+    #[test]
+    fn decompile_never_panics((tables, tx) in arbitrary_transaction()) {
+        let accounts = tables
+            .iter()
+            .map(|(key, addresses)| (*key, active_table_account(addresses)))
+            .collect::<Vec<_>>();
+        let loader = BasicAddressLoader::from_loaded_accounts(&accounts).unwrap();
+
+        let _ = decompile_transaction_instructions(tx.clone(), Some(&loader));
+        let _ = decompile_transaction_instructions(tx, None::<&BasicAddressLoader>);
+    }
+
+    #[test]
+    fn loader_never_panics_on_arbitrary_table_data(
+        key in pubkey(),
+        data in prop::collection::vec(any::<u8>(), 0..128),
+    ) {
+        let accounts = vec![(key, Account { data, ..Account::default() })];
+        let _ = BasicAddressLoader::from_loaded_accounts(&accounts);
+    }
+
+    #[test]
+    fn decompile_recovers_compiled_instructions(
+        payer in pubkey(),
+        program_ids in prop::collection::vec(pubkey(), 1..4),
+        table_addresses in prop::collection::vec(pubkey(), 0..8),
+        extra_accounts in prop::collection::vec((pubkey(), any::<bool>()), 0..6),
+        data in prop::collection::vec(any::<u8>(), 0..16),
+    ) {
+        let table_key = Pubkey::new_unique();
+        let instructions = program_ids
+            .iter()
+            .map(|program_id| {
+                let accounts = std::iter::once(AccountMeta::new(payer, true))
+                    .chain(table_addresses.iter().map(|key| AccountMeta::new(*key, false)))
+                    .chain(extra_accounts.iter().map(|(key, is_writable)| AccountMeta {
+                        pubkey: *key,
+                        is_signer: false,
+                        is_writable: *is_writable,
+                    }))
+                    .collect();
+                Instruction::new_with_bytes(*program_id, &data, accounts)
+            })
+            .collect::<Vec<_>>();
+        let lookup_table = AddressLookupTableAccount {
+            key: table_key,
+            addresses: table_addresses.clone(),
+        };
+        let Ok(message) =
+            v0::Message::try_compile(&payer, &instructions, &[lookup_table], Hash::default())
+        else {
+            return Ok(());
+        };
+        let tx = VersionedTransaction {
+            signatures: vec![],
+            message: VersionedMessage::V0(message),
+        };
+        let accounts = vec![(table_key, active_table_account(&table_addresses))];
+        let loader = BasicAddressLoader::from_loaded_accounts(&accounts).unwrap();
+
+        let decompiled = decompile_transaction_instructions(tx, Some(&loader)).unwrap();
+
+        prop_assert_eq!(decompiled.len(), instructions.len());
+        for (decompiled, original) in decompiled.iter().zip(&instructions) {
+            prop_assert_eq!(decompiled.program_id, original.program_id);
+            prop_assert_eq!(&decompiled.data, &original.data);
+            let decompiled_keys = decompiled.accounts.iter().map(|meta| meta.pubkey);
+            let original_keys = original.accounts.iter().map(|meta| meta.pubkey);
+            prop_assert!(decompiled_keys.eq(original_keys));
+        }
+    }
+}
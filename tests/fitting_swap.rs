@@ -0,0 +1,338 @@
+use std::sync::{Arc, Mutex};
+
+use common::{active_table_account, MockFetcher};
+use juno::tx_size::MAX_TX_ACCOUNT_LOCKS;
+use juno::{
+    Configuration, Error, JupiterClient, QuoteRequest, RetryPolicy, SwapOptions, TxComposer,
+};
+use serde_json::{json, Value};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::CompileError;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::pubkey::Pubkey;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+mod common;
+
+const QUOTE: &str = include_str!("fixtures/quote.json");
+
+/// Quotes a route using as many accounts as allowed, stored in `contextSlot` for the swap
+/// responder, direct routes use `direct_accounts`, none are found when it is 0
+struct QuoteResponder {
+    /// `maxAccounts` below which only direct routes are found
+    min_multi_hop_accounts: u8,
+    direct_accounts: u8,
+    requests: Arc<Mutex<Vec<(u8, bool)>>>,
+}
+
+impl Respond for QuoteResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let query = |name: &str| {
+            request
+                .url
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+        let max_accounts = query("maxAccounts").unwrap().parse::<u8>().unwrap();
+        let direct = query("onlyDirectRoutes").as_deref() == Some("true");
+        self.requests.lock().unwrap().push((max_accounts, direct));
+
+        let accounts = if direct && self.direct_accounts > 0 {
+            self.direct_accounts.min(max_accounts)
+        } else if !direct && max_accounts >= self.min_multi_hop_accounts {
+            max_accounts
+        } else {
+            return ResponseTemplate::new(400).set_body_string(
+                r#"{"error":"Could not find any route","errorCode":"COULD_NOT_FIND_ANY_ROUTE"}"#,
+            );
+        };
+        let mut quote = serde_json::from_str::<Value>(QUOTE).unwrap();
+        quote["contextSlot"] = json!(accounts);
+        ResponseTemplate::new(200).set_body_json(quote)
+    }
+}
+
+/// Returns a swap instruction with as many new accounts as the quote `contextSlot`
+struct SwapInstructionsResponder;
+
+impl Respond for SwapInstructionsResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body = serde_json::from_slice::<Value>(&request.body).unwrap();
+        let accounts = body["quoteResponse"]["contextSlot"].as_u64().unwrap();
+        let user = body["userPublicKey"].as_str().unwrap();
+        let mut metas = vec![json!({"pubkey": user, "isSigner": true, "isWritable": true})];
+        metas.extend((1..accounts).map(|_| {
+            json!({
+                "pubkey": Pubkey::new_unique().to_string(),
+                "isSigner": false,
+                "isWritable": true,
+            })
+        }));
+        ResponseTemplate::new(200).set_body_json(json!({
+            "swapInstruction": {
+                "programId": Pubkey::new_unique().to_string(),
+                "accounts": metas,
+                "data": "AQID",
+            },
+        }))
+    }
+}
+
+/// Returns a swap instruction using every address of `table`
+struct LookupTableSwapResponder {
+    table: Pubkey,
+    addresses: Vec<Pubkey>,
+}
+
+impl Respond for LookupTableSwapResponder {
+    fn respond(&self, _: &Request) -> ResponseTemplate {
+        let metas = self
+            .addresses
+            .iter()
+            .map(|pubkey| {
+                json!({"pubkey": pubkey.to_string(), "isSigner": false, "isWritable": false})
+            })
+            .collect::<Vec<_>>();
+        ResponseTemplate::new(200).set_body_json(json!({
+            "swapInstruction": {
+                "programId": Pubkey::new_unique().to_string(),
+                "accounts": metas,
+                "data": "AQID",
+            },
+            "addressLookupTableAddresses": [self.table.to_string()],
+        }))
+    }
+}
+
+async fn client(
+    min_multi_hop_accounts: u8,
+    direct_accounts: u8,
+) -> (MockServer, JupiterClient, Arc<Mutex<Vec<(u8, bool)>>>) {
+    client_with_swap_responder(
+        min_multi_hop_accounts,
+        direct_accounts,
+        SwapInstructionsResponder,
+    )
+    .await
+}
+
+async fn client_with_swap_responder(
+    min_multi_hop_accounts: u8,
+    direct_accounts: u8,
+    swap_responder: impl Respond + 'static,
+) -> (MockServer, JupiterClient, Arc<Mutex<Vec<(u8, bool)>>>) {
+    let server = MockServer::start().await;
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(QuoteResponder {
+            min_multi_hop_accounts,
+            direct_accounts,
+            requests: requests.clone(),
+        })
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/swap-instructions"))
+        .respond_with(swap_responder)
+        .mount(&server)
+        .await;
+    let configuration = Configuration::new(server.uri()).with_retry_policy(RetryPolicy::none());
+    let client = JupiterClient::with_configuration(configuration);
+    (server, client, requests)
+}
+
+fn caller_instruction(user: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        Pubkey::new_unique(),
+        &[0; 32],
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(Pubkey::new_unique(), false),
+        ],
+    )
+}
+
+fn request() -> QuoteRequest {
+    QuoteRequest::new(Pubkey::new_unique(), Pubkey::new_unique(), 1_000_000)
+}
+
+#[tokio::test]
+async fn max_accounts_decreases_until_the_swap_fits() {
+    let (_server, client, requests) = client(0, 0).await;
+    let user = Pubkey::new_unique();
+    let composer = TxComposer::new(user)
+        .pre_instructions([caller_instruction(&user)])
+        .post_instructions([caller_instruction(&user)]);
+
+    let fitted = client
+        .get_fitting_swap_transaction(
+            &request(),
+            None,
+            user,
+            &SwapOptions::default(),
+            &composer,
+            MAX_TX_ACCOUNT_LOCKS,
+            &MockFetcher::default(),
+        )
+        .await
+        .unwrap();
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests[0], (juno::DEFAULT_MAX_ACCOUNTS, false));
+    assert!(requests.len() > 1);
+    assert!(requests.windows(2).all(|pair| pair[1].0 < pair[0].0));
+    assert!(requests.iter().all(|(_, direct)| !direct));
+    assert!(fitted.estimate.fits());
+    assert_eq!(
+        fitted.route.context_slot,
+        u64::from(requests.last().unwrap().0)
+    );
+    let size = bincode::serialized_size(&fitted.transaction).unwrap() as usize;
+    assert_eq!(size, fitted.estimate.size);
+    assert!(size <= PACKET_DATA_SIZE);
+    assert_eq!(fitted.transaction.message.instructions().len(), 3);
+}
+
+#[tokio::test]
+async fn fitting_routes_are_returned_without_requote() {
+    let (_server, client, requests) = client(0, 0).await;
+    let user = Pubkey::new_unique();
+
+    let fitted = client
+        .get_fitting_swap_transaction(
+            &request().max_accounts(20),
+            None,
+            user,
+            &SwapOptions::default(),
+            &TxComposer::new(user),
+            MAX_TX_ACCOUNT_LOCKS,
+            &MockFetcher::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(*requests.lock().unwrap(), [(20, false)]);
+    assert_eq!(fitted.estimate.swap_account_count, 20);
+}
+
+#[tokio::test]
+async fn direct_routes_are_tried_when_no_smaller_route_is_found() {
+    let (_server, client, requests) = client(60, 10).await;
+    let user = Pubkey::new_unique();
+
+    let fitted = client
+        .get_fitting_swap_transaction(
+            &request(),
+            None,
+            user,
+            &SwapOptions::default(),
+            &TxComposer::new(user),
+            MAX_TX_ACCOUNT_LOCKS,
+            &MockFetcher::default(),
+        )
+        .await
+        .unwrap();
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 3);
+    assert!(!requests[1].1);
+    assert!(requests[2].1);
+    assert_eq!(fitted.route.context_slot, 10);
+}
+
+#[tokio::test]
+async fn swaps_that_never_fit_are_reported() {
+    let (_server, client, requests) = client(0, 60).await;
+    let user = Pubkey::new_unique();
+    // Leaves no room for the swap accounts
+    let composer = TxComposer::new(user).pre_instructions([Instruction::new_with_bytes(
+        Pubkey::new_unique(),
+        &[0; 900],
+        vec![AccountMeta::new(user, true)],
+    )]);
+
+    let error = client
+        .get_fitting_swap_transaction(
+            &request(),
+            None,
+            user,
+            &SwapOptions::default(),
+            &composer,
+            MAX_TX_ACCOUNT_LOCKS,
+            &MockFetcher::default(),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, Error::SwapDoesNotFit { size, .. } if size > PACKET_DATA_SIZE),
+        "{error:?}"
+    );
+    assert!(requests.lock().unwrap().last().unwrap().1);
+}
+
+#[tokio::test]
+async fn oversized_routes_are_reported_when_no_direct_route_exists() {
+    let (_server, client, requests) = client(0, 0).await;
+    let user = Pubkey::new_unique();
+    let composer = TxComposer::new(user).pre_instructions([Instruction::new_with_bytes(
+        Pubkey::new_unique(),
+        &[0; 900],
+        vec![AccountMeta::new(user, true)],
+    )]);
+
+    let error = client
+        .get_fitting_swap_transaction(
+            &request(),
+            None,
+            user,
+            &SwapOptions::default(),
+            &composer,
+            MAX_TX_ACCOUNT_LOCKS,
+            &MockFetcher::default(),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, Error::SwapDoesNotFit { size, .. } if size > PACKET_DATA_SIZE),
+        "{error:?}"
+    );
+    assert!(requests.lock().unwrap().last().unwrap().1);
+}
+
+#[tokio::test]
+async fn compile_errors_are_returned_without_requote() {
+    let table = Pubkey::new_unique();
+    let addresses = (0..300).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let fetcher = MockFetcher::new([(table, active_table_account(&addresses))]);
+    let responder = LookupTableSwapResponder { table, addresses };
+    let (_server, client, requests) = client_with_swap_responder(0, 0, responder).await;
+    let user = Pubkey::new_unique();
+
+    // Small enough with the lookup table but too many addresses to index
+    let error = client
+        .get_fitting_swap_transaction(
+            &request(),
+            None,
+            user,
+            &SwapOptions::default(),
+            &TxComposer::new(user),
+            usize::MAX,
+            &fetcher,
+        )
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(
+            error,
+            Error::SolanaCompile(CompileError::AddressTableLookupIndexOverflow)
+        ),
+        "{error:?}"
+    );
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
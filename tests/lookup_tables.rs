@@ -0,0 +1,151 @@
+use common::MockFetcher;
+use juno::utils::{BasicAddressLoader, LookupTableIssue, UnsafeLookupTable};
+use juno::{CachingAccountFetcher, Error};
+use solana_program::address_lookup_table::state::LookupTableMeta;
+use solana_sdk::{account::Account, pubkey::Pubkey, slot_hashes::MAX_ENTRIES};
+
+mod common;
+
+const QUOTE_SLOT: u64 = 250_000_000;
+
+fn table_account(meta: LookupTableMeta) -> Account {
+    common::table_account(meta, &[Pubkey::new_unique(); 4])
+}
+
+fn loader(metas: Vec<LookupTableMeta>) -> (Vec<Pubkey>, BasicAddressLoader<'static>) {
+    let keys = metas
+        .iter()
+        .map(|_| Pubkey::new_unique())
+        .collect::<Vec<_>>();
+    let accounts = metas.into_iter().map(table_account).collect::<Vec<_>>();
+    let loader = BasicAddressLoader::from_accounts_and_accounts_fetcher(&keys, |_| {
+        Ok(accounts.iter().cloned().map(Some).collect())
+    })
+    .unwrap();
+    (keys, loader)
+}
+
+#[test]
+fn settled_tables_are_safe() {
+    let (_, loader) = loader(vec![LookupTableMeta {
+        last_extended_slot: QUOTE_SLOT - 1,
+        ..LookupTableMeta::default()
+    }]);
+
+    assert_eq!(
+        loader.check_tables(QUOTE_SLOT, Some(QUOTE_SLOT)).unwrap(),
+        vec![]
+    );
+}
+
+#[test]
+fn deactivating_and_freshly_extended_tables_are_reported() {
+    let (keys, loader) = loader(vec![
+        LookupTableMeta {
+            deactivation_slot: QUOTE_SLOT - 10,
+            ..LookupTableMeta::default()
+        },
+        LookupTableMeta {
+            last_extended_slot: QUOTE_SLOT,
+            last_extended_slot_start_index: 2,
+            ..LookupTableMeta::default()
+        },
+    ]);
+
+    let mut unsafe_tables = loader.check_tables(QUOTE_SLOT, Some(QUOTE_SLOT)).unwrap();
+    unsafe_tables.sort_by_key(|table| keys.iter().position(|key| *key == table.key));
+    assert_eq!(
+        unsafe_tables,
+        vec![
+            UnsafeLookupTable {
+                key: keys[0],
+                issue: LookupTableIssue::Deactivating {
+                    deactivation_slot: QUOTE_SLOT - 10
+                },
+            },
+            UnsafeLookupTable {
+                key: keys[1],
+                issue: LookupTableIssue::ExtendedAfter {
+                    last_extended_slot: QUOTE_SLOT,
+                    start_index: 2
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn deactivated_tables_are_rejected() {
+    let (keys, loader) = loader(vec![LookupTableMeta {
+        deactivation_slot: QUOTE_SLOT - MAX_ENTRIES as u64 - 1,
+        ..LookupTableMeta::default()
+    }]);
+
+    assert!(matches!(
+        loader.check_tables(QUOTE_SLOT, Some(QUOTE_SLOT)),
+        Err(Error::LookupTableDeactivated(key)) if key == keys[0]
+    ));
+}
+
+#[test]
+fn deactivation_uses_the_current_slot_and_extension_the_quote_slot() {
+    let current_slot = QUOTE_SLOT + MAX_ENTRIES as u64;
+    let (keys, extended) = loader(vec![LookupTableMeta {
+        last_extended_slot: QUOTE_SLOT + 5,
+        last_extended_slot_start_index: 3,
+        ..LookupTableMeta::default()
+    }]);
+
+    assert_eq!(
+        extended
+            .check_tables(current_slot, Some(QUOTE_SLOT))
+            .unwrap(),
+        vec![UnsafeLookupTable {
+            key: keys[0],
+            issue: LookupTableIssue::ExtendedAfter {
+                last_extended_slot: QUOTE_SLOT + 5,
+                start_index: 3
+            },
+        }]
+    );
+    assert_eq!(extended.check_tables(current_slot, None).unwrap(), vec![]);
+
+    // Still usable when the quote was made, deactivated by now
+    let (_, deactivating) = loader(vec![LookupTableMeta {
+        deactivation_slot: QUOTE_SLOT - 10,
+        ..LookupTableMeta::default()
+    }]);
+    assert!(deactivating
+        .check_tables(QUOTE_SLOT, Some(QUOTE_SLOT))
+        .is_ok());
+    assert!(matches!(
+        deactivating.check_tables(current_slot, Some(QUOTE_SLOT)),
+        Err(Error::LookupTableDeactivated(_))
+    ));
+}
+
+#[tokio::test]
+async fn slot_aware_constructor_serves_cached_tables() {
+    let key = Pubkey::new_unique();
+    let meta = LookupTableMeta {
+        last_extended_slot: QUOTE_SLOT - 100,
+        ..LookupTableMeta::default()
+    };
+    let fetcher = CachingAccountFetcher::new(MockFetcher::new([(key, table_account(meta))]));
+    juno::AsyncAccountFetcher::fech_accounts(&fetcher, &[key])
+        .await
+        .unwrap();
+
+    let (_, unsafe_tables) = BasicAddressLoader::from_accounts_and_async_accounts_fetcher_at_slot(
+        &[key],
+        &fetcher,
+        QUOTE_SLOT + 10,
+        Some(QUOTE_SLOT),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(unsafe_tables, vec![]);
+    // Append only, the cached copy is good for later quotes
+    assert_eq!(fetcher.inner().fetched(), vec![key]);
+}
@@ -0,0 +1,81 @@
+use common::{mint_account, MockFetcher};
+use juno::mint::{
+    amount_to_ui_amount, mint_decimals, ui_amount_to_amount, MintDecimalsCache,
+    TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+use juno::reexports::Decimal;
+use juno::Error;
+use solana_sdk::pubkey::Pubkey;
+
+mod common;
+
+fn ui(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+#[test]
+fn decimals_are_read_from_token_and_token_2022_mints() {
+    let mint = Pubkey::new_unique();
+
+    assert_eq!(
+        mint_decimals(&mint, &mint_account(TOKEN_PROGRAM_ID, 6, 0)).unwrap(),
+        6
+    );
+    assert_eq!(
+        mint_decimals(&mint, &mint_account(TOKEN_2022_PROGRAM_ID, 9, 120)).unwrap(),
+        9
+    );
+    assert!(matches!(
+        mint_decimals(&mint, &mint_account(Pubkey::new_unique(), 6, 0)),
+        Err(Error::InvalidMintAccount(_))
+    ));
+    let mut uninitialized = mint_account(TOKEN_PROGRAM_ID, 6, 0);
+    uninitialized.data[45] = 0;
+    assert!(mint_decimals(&mint, &uninitialized).is_err());
+}
+
+#[test]
+fn ui_amounts_convert_losslessly() {
+    assert_eq!(amount_to_ui_amount(1_234_567, 6).unwrap(), ui("1.234567"));
+    assert_eq!(
+        amount_to_ui_amount(u64::MAX, 9).unwrap(),
+        ui("18446744073.709551615")
+    );
+    assert_eq!(ui_amount_to_amount(ui("1.234567"), 6).unwrap(), 1_234_567);
+    assert_eq!(ui_amount_to_amount(ui("1.50000000"), 6).unwrap(), 1_500_000);
+    assert_eq!(ui_amount_to_amount(ui("42"), 0).unwrap(), 42);
+
+    for (ui_amount, decimals) in [("1.2345678", 6), ("-1", 6), ("18446744073709551616", 0)] {
+        assert!(
+            matches!(
+                ui_amount_to_amount(ui(ui_amount), decimals),
+                Err(Error::InvalidUiAmount(_))
+            ),
+            "{ui_amount} accepted"
+        );
+    }
+}
+
+#[tokio::test]
+async fn decimals_are_fetched_once() {
+    let (usdh, token_2022) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let fetcher = MockFetcher::new([
+        (usdh, mint_account(TOKEN_PROGRAM_ID, 6, 0)),
+        (token_2022, mint_account(TOKEN_2022_PROGRAM_ID, 9, 64)),
+    ]);
+    let cache = MintDecimalsCache::new();
+
+    let decimals = cache
+        .get_or_fetch(&[usdh, token_2022, usdh], &fetcher)
+        .await
+        .unwrap();
+    assert_eq!(decimals, vec![6, 9, 6]);
+    cache.get_or_fetch(&[token_2022], &fetcher).await.unwrap();
+
+    assert_eq!(fetcher.fetched().len(), 2);
+    let missing = Pubkey::new_unique();
+    assert!(matches!(
+        cache.get_or_fetch(&[missing], &fetcher).await,
+        Err(Error::MintAccountNotFound(key)) if key == missing
+    ));
+}
@@ -0,0 +1,132 @@
+use juno::models::price_v2::{ConfidenceLevel, PriceV2Response};
+use juno::reexports::Decimal;
+use juno::{Configuration, JupiterClient, PriceApiVersion, RetryPolicy};
+use solana_sdk::pubkey::Pubkey;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const PRICE_V2: &str = include_str!("fixtures/price_v2.json");
+const SOL: &str = "So11111111111111111111111111111111111111112";
+const JUP: &str = "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN";
+const BONK: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+
+fn decimal(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+fn client(server: &MockServer, version: PriceApiVersion) -> JupiterClient {
+    JupiterClient::with_configuration(
+        Configuration::new(server.uri())
+            .with_price_api_version(version)
+            .with_retry_policy(RetryPolicy::none()),
+    )
+}
+
+#[test]
+fn extra_info_is_parsed() {
+    let response: PriceV2Response = serde_json::from_str(PRICE_V2).unwrap();
+
+    assert_eq!(response.data[BONK], None);
+    let sol = response.data[SOL].as_ref().unwrap();
+    assert_eq!(sol.price, decimal("133.175271900"));
+    let extra_info = sol.extra_info.as_ref().unwrap();
+    assert_eq!(extra_info.confidence_level, Some(ConfidenceLevel::High));
+    let last_swapped = extra_info.last_swapped_price.as_ref().unwrap();
+    assert_eq!(
+        last_swapped.last_jupiter_sell_price,
+        Some(decimal("132.99286502147118"))
+    );
+    assert_eq!(
+        extra_info.quoted_price.as_ref().unwrap().buy_at,
+        Some(1726231878)
+    );
+    let depth = extra_info.depth.as_ref().unwrap();
+    let buy_depth = &depth.buy_price_impact_ratio.as_ref().unwrap().depth;
+    assert_eq!(
+        buy_depth.keys().copied().collect::<Vec<_>>(),
+        vec![10, 100, 1000]
+    );
+    assert_eq!(buy_depth[&1000], decimal("0.004013856471"));
+
+    let jup = response.data[JUP]
+        .as_ref()
+        .unwrap()
+        .extra_info
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        jup.last_swapped_price
+            .as_ref()
+            .unwrap()
+            .last_jupiter_sell_price,
+        None
+    );
+    assert_eq!(jup.depth, None);
+
+    let round_trip: PriceV2Response =
+        serde_json::from_str(&serde_json::to_string(&response).unwrap()).unwrap();
+    assert_eq!(round_trip, response);
+}
+
+#[tokio::test]
+async fn price_details_skip_unknown_ids() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/price/v2"))
+        .and(query_param("ids", format!("{SOL},{JUP},{BONK}")))
+        .and(query_param("showExtraInfo", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(PRICE_V2))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ids = [SOL, JUP, BONK].map(|id| id.parse::<Pubkey>().unwrap());
+    let prices = client(&server, PriceApiVersion::V4)
+        .get_price_details(&ids, None, true)
+        .await
+        .unwrap();
+
+    assert_eq!(prices.len(), 2);
+    assert_eq!(
+        prices[JUP].extra_info.as_ref().unwrap().confidence_level,
+        Some(ConfidenceLevel::Medium)
+    );
+}
+
+#[tokio::test]
+async fn get_prices_targets_the_configured_version() {
+    let server = MockServer::start().await;
+    let vs_token = Pubkey::new_unique();
+    Mock::given(method("GET"))
+        .and(path("/price/v2"))
+        .and(query_param("vsToken", vs_token.to_string()))
+        .respond_with(ResponseTemplate::new(200).set_body_string(PRICE_V2))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v6/price"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(format!(
+                r#"{{"data":{{"{SOL}":{{"id":"{SOL}","mintSymbol":"SOL","vsToken":"{vs_token}","vsTokenSymbol":"X","price":133.1752719}}}},"timeTaken":0.0003}}"#
+            )),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    let sol = SOL.parse::<Pubkey>().unwrap();
+
+    let prices = client(&server, PriceApiVersion::V2)
+        .get_prices(&[sol], &vs_token, Decimal::ONE)
+        .await
+        .unwrap();
+    assert_eq!(prices[SOL].price, decimal("133.175271900"));
+    assert_eq!(prices[SOL].vs_token, vs_token);
+
+    let prices = client(&server, PriceApiVersion::V6)
+        .get_prices(&[sol], &vs_token, Decimal::ONE)
+        .await
+        .unwrap();
+    assert_eq!(prices[SOL].price, decimal("133.1752719"));
+    assert_eq!(prices[SOL].mint_symbol.as_deref(), Some("SOL"));
+}
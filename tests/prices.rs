@@ -0,0 +1,158 @@
+use juno::reexports::Decimal;
+use juno::{Configuration, Error, JupiterClient, PriceApiVersion, PriceId, RetryPolicy, SwapPrice};
+use solana_sdk::pubkey::Pubkey;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn decimal(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+fn price_json(id: &Pubkey, vs_token: &Pubkey, price: &str) -> String {
+    format!(
+        r#"{{"id":"{id}","mintSymbol":"BONK","vsToken":"{vs_token}","vsTokenSymbol":"USDC","price":{price}}}"#
+    )
+}
+
+#[test]
+fn prices_are_parsed_without_float_rounding() {
+    let (id, vs_token) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    for (raw, expected) in [
+        ("0.000012345678912345", "0.000012345678912345"),
+        ("123456789.123456789", "123456789.123456789"),
+        ("1.2345e-8", "0.000000012345"),
+        ("\"0.1\"", "0.1"),
+        ("42", "42"),
+    ] {
+        let price: SwapPrice = serde_json::from_str(&price_json(&id, &vs_token, raw)).unwrap();
+        assert_eq!(price.price, decimal(expected), "{raw}");
+    }
+
+    let price: SwapPrice =
+        serde_json::from_str(&price_json(&id, &vs_token, "0.000012345678912345")).unwrap();
+    assert_eq!(price.price_f64(), 0.000012345678912345);
+    assert_eq!(price.price_f32(), 0.000012345679_f32);
+    let round_trip: SwapPrice =
+        serde_json::from_str(&serde_json::to_string(&price).unwrap()).unwrap();
+    assert_eq!(round_trip, price);
+
+    assert!(serde_json::from_str::<SwapPrice>(&price_json(&id, &vs_token, "true")).is_err());
+}
+
+#[tokio::test]
+async fn vs_amount_is_sent_without_float_rounding() {
+    let (id, vs_token) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v4/price"))
+        .and(query_param("ids", id.to_string()))
+        .and(query_param("vsAmount", "1000000.000001"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+            r#"{{"data":{{"{id}":{}}},"timeTaken":0.0005}}"#,
+            price_json(&id, &vs_token, "0.000012345678912345")
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = JupiterClient::with_configuration(
+        Configuration::new(server.uri())
+            .with_price_base_path(server.uri())
+            .with_retry_policy(RetryPolicy::none()),
+    );
+    let prices = client
+        .get_prices(&[id], &vs_token, decimal("1000000.000001"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        prices[&id.to_string()].price,
+        decimal("0.000012345678912345")
+    );
+}
+
+#[tokio::test]
+async fn prices_can_be_looked_up_by_symbol() {
+    let (sol, usdc) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v4/price"))
+        .and(query_param("ids", format!("SOL,{sol}")))
+        .and(query_param("vsToken", "USDC"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+            r#"{{"data":{{"SOL":{},"{sol}":{}}}}}"#,
+            price_json(&sol, &usdc, "133.17"),
+            price_json(&sol, &usdc, "133.17")
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = JupiterClient::with_configuration(
+        Configuration::new(server.uri()).with_retry_policy(RetryPolicy::none()),
+    );
+    let ids = ["SOL", &sol.to_string()].map(|id| id.parse::<PriceId>().unwrap());
+    assert_eq!(ids[1], PriceId::Mint(sol));
+    let prices = client
+        .get_prices_by_id(&ids, &PriceId::symbol("USDC"), Decimal::ONE)
+        .await
+        .unwrap();
+
+    assert_eq!(prices["SOL"].id, sol);
+    assert_eq!(prices["SOL"], prices[&sol.to_string()]);
+}
+
+#[tokio::test]
+async fn duplicated_symbol_lists_the_candidates() {
+    let candidates = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v4/price"))
+        .respond_with(ResponseTemplate::new(409).set_body_string(format!(
+            r#"{{"data":{{"error":"Duplicated symbol found for PERP, use one of the address instead","addresses":["{}","{}"]}}}}"#,
+            candidates[0], candidates[1]
+        )))
+        .mount(&server)
+        .await;
+
+    let client = JupiterClient::with_configuration(
+        Configuration::new(server.uri()).with_retry_policy(RetryPolicy::none()),
+    );
+    let err = client
+        .get_prices_by_id(
+            &[PriceId::symbol("PERP")],
+            &PriceId::symbol("USDC"),
+            Decimal::ONE,
+        )
+        .await
+        .unwrap_err();
+
+    match err {
+        Error::AmbiguousPriceSymbol { message, addresses } => {
+            assert!(message.contains("PERP"));
+            assert_eq!(addresses, candidates);
+        }
+        err => panic!("unexpected error {err:?}"),
+    }
+}
+
+#[tokio::test]
+async fn symbols_are_rejected_by_the_v2_api() {
+    let client = JupiterClient::with_configuration(
+        Configuration::new("http://127.0.0.1:1").with_price_api_version(PriceApiVersion::V2),
+    );
+
+    let err = client
+        .get_prices_by_id(
+            &[PriceId::symbol("SOL")],
+            &PriceId::from(Pubkey::new_unique()),
+            Decimal::ONE,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::PriceSymbolUnsupported(PriceApiVersion::V2)
+    ));
+}
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use juno::{Configuration, JupiterClient, QuoteRequest, RetryPolicy, SwapMode};
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const USDH: Pubkey = pubkey!("USDH1SM1ojwWUga67PGrgFWUHibbjqMvuMaDkRJTgkX");
+const HBB: Pubkey = pubkey!("HBB111SCo9jkCejsZfz8Ec8nH7T6THF8KEKSnvwT6XK6");
+const QUOTE: &str = include_str!("fixtures/quote.json");
+
+async fn sent_query(request: &QuoteRequest, expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    let server = MockServer::start().await;
+    let mock = expected.iter().fold(
+        Mock::given(method("GET")).and(path("/quote")),
+        |mock, (name, value)| mock.and(query_param(*name, *value)),
+    );
+    mock.respond_with(ResponseTemplate::new(200).set_body_string(QUOTE))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    JupiterClient::with_configuration(
+        Configuration::new(server.uri()).with_retry_policy(RetryPolicy::none()),
+    )
+    .get_quote(request)
+    .await
+    .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    requests[0]
+        .url
+        .query_pairs()
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect()
+}
+
+#[tokio::test]
+async fn every_parameter_is_sent_with_the_api_names() {
+    let request = QuoteRequest::new(USDH, HBB, 1_000_000)
+        .slippage_bps(50)
+        .swap_mode(SwapMode::ExactOut)
+        .dexes(["Orca V2", "Raydium"])
+        .exclude_dexes(["Lifinity V2"])
+        .restrict_intermediate_tokens(true)
+        .only_direct_routes(false)
+        .as_legacy_transaction(true)
+        .platform_fee_bps(20)
+        .max_accounts(32)
+        .timeout(Duration::from_secs(3));
+    let expected = [
+        ("inputMint", "USDH1SM1ojwWUga67PGrgFWUHibbjqMvuMaDkRJTgkX"),
+        ("outputMint", "HBB111SCo9jkCejsZfz8Ec8nH7T6THF8KEKSnvwT6XK6"),
+        ("amount", "1000000"),
+        ("slippageBps", "50"),
+        ("swapMode", "ExactOut"),
+        ("dexes", "Orca V2,Raydium"),
+        ("excludeDexes", "Lifinity V2"),
+        ("restrictIntermediateTokens", "true"),
+        ("onlyDirectRoutes", "false"),
+        ("asLegacyTransaction", "true"),
+        ("platformFeeBps", "20"),
+        ("maxAccounts", "32"),
+    ];
+
+    let query = sent_query(&request, &expected).await;
+
+    // Nothing else, the deadline included
+    let expected = expected
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(query, expected);
+}
+
+#[tokio::test]
+async fn unset_parameters_are_not_sent() {
+    let request = QuoteRequest::new(USDH, HBB, 1);
+
+    let query = sent_query(&request, &[("amount", "1")]).await;
+
+    let names = query
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["inputMint", "outputMint", "amount"]);
+}
@@ -0,0 +1,232 @@
+use std::time::{Duration, Instant, SystemTime};
+
+use juno::{Configuration, Error, JupiterClient, QuoteRequest, RetryPolicy};
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const USDH: Pubkey = pubkey!("USDH1SM1ojwWUga67PGrgFWUHibbjqMvuMaDkRJTgkX");
+const HBB: Pubkey = pubkey!("HBB111SCo9jkCejsZfz8Ec8nH7T6THF8KEKSnvwT6XK6");
+const QUOTE: &str = include_str!("fixtures/quote.json");
+
+fn fast_retry_policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+    }
+}
+
+fn client(server: &MockServer, retry_policy: RetryPolicy) -> JupiterClient {
+    JupiterClient::with_configuration(
+        Configuration::new(server.uri()).with_retry_policy(retry_policy),
+    )
+}
+
+fn quote_request() -> QuoteRequest {
+    QuoteRequest::new(USDH, HBB, 1_000_000)
+}
+
+async fn received_requests(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap_or_default().len()
+}
+
+#[tokio::test]
+async fn retries_rate_limited_requests_until_success() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(QUOTE))
+        .mount(&server)
+        .await;
+
+    let quote = client(&server, fast_retry_policy(3))
+        .get_quote(&quote_request())
+        .await
+        .unwrap();
+
+    assert_eq!(quote.in_amount, 1_000_000);
+    assert_eq!(received_requests(&server).await, 3);
+}
+
+/// Server answering 429 with `retry_after` once, then the quote
+async fn rate_limited_once(retry_after: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", retry_after))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(QUOTE))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn retry_after_longer_than_the_max_backoff_is_returned() {
+    let server = rate_limited_once("3600").await;
+
+    let start = Instant::now();
+    let err = client(&server, fast_retry_policy(3))
+        .get_quote(&quote_request())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::RateLimited(_)), "{err:?}");
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(received_requests(&server).await, 1);
+}
+
+#[tokio::test]
+async fn retry_after_past_the_deadline_is_returned() {
+    let server = rate_limited_once("2").await;
+    let policy = RetryPolicy {
+        max_backoff: Duration::from_secs(3600),
+        ..fast_retry_policy(3)
+    };
+
+    let start = Instant::now();
+    let err = client(&server, policy)
+        .get_quote(&quote_request().timeout(Duration::from_secs(1)))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::RateLimited(_)), "{err:?}");
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(received_requests(&server).await, 1);
+}
+
+#[tokio::test]
+async fn retry_after_http_dates_are_honored() {
+    let future = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(3600));
+    let server = rate_limited_once(&future).await;
+
+    let err = client(&server, fast_retry_policy(3))
+        .get_quote(&quote_request())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::RateLimited(_)), "{err:?}");
+    assert_eq!(received_requests(&server).await, 1);
+
+    let past = httpdate::fmt_http_date(SystemTime::now() - Duration::from_secs(60));
+    let server = rate_limited_once(&past).await;
+
+    client(&server, fast_retry_policy(3))
+        .get_quote(&quote_request())
+        .await
+        .unwrap();
+
+    assert_eq!(received_requests(&server).await, 2);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts_on_server_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(ResponseTemplate::new(503).set_body_string("unavailable"))
+        .mount(&server)
+        .await;
+
+    let err = client(&server, fast_retry_policy(4))
+        .get_quote(&quote_request())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::JupiterServer(_)), "{err:?}");
+    assert_eq!(received_requests(&server).await, 4);
+}
+
+#[tokio::test]
+async fn does_not_retry_client_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(ResponseTemplate::new(400).set_body_string(
+            r#"{"error":"Could not find any route","errorCode":"COULD_NOT_FIND_ANY_ROUTE"}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let err = client(&server, fast_retry_policy(3))
+        .get_quote(&quote_request())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::NoValidRoute), "{err:?}");
+    assert_eq!(received_requests(&server).await, 1);
+}
+
+#[tokio::test]
+async fn retries_swap_posts() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/swap"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/swap"))
+        .respond_with(ResponseTemplate::new(400).set_body_string(r#"{"error":"bad"}"#))
+        .mount(&server)
+        .await;
+
+    let route: juno::SwapRoute = serde_json::from_str(QUOTE).unwrap();
+    let err = client(&server, fast_retry_policy(3))
+        .get_swap_transactions(route, Pubkey::new_unique(), &Default::default())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::JupiterApi(_)), "{err:?}");
+    assert_eq!(received_requests(&server).await, 2);
+}
+
+#[tokio::test]
+async fn rate_limiter_is_shared_between_clones() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(QUOTE))
+        .mount(&server)
+        .await;
+
+    let configuration = Configuration::new(server.uri())
+        .with_retry_policy(RetryPolicy::none())
+        .with_rate_limit(10.0, 1);
+    let first = JupiterClient::with_configuration(configuration.clone());
+    let second = JupiterClient::with_configuration(configuration);
+
+    let start = Instant::now();
+    for client in [&first, &second, &first, &second] {
+        client.get_quote(&quote_request()).await.unwrap();
+    }
+
+    // One token available right away, then one every 100ms
+    assert!(start.elapsed() >= Duration::from_millis(290));
+    assert_eq!(received_requests(&server).await, 4);
+}
+
+#[test]
+#[should_panic(expected = "requests_per_second must be a finite positive number")]
+fn zero_rate_limits_are_rejected() {
+    let _ = Configuration::new("http://localhost").with_rate_limit(0.0, 1);
+}
+
+#[test]
+#[should_panic(expected = "requests_per_second must be a finite positive number")]
+fn nan_rate_limits_are_rejected() {
+    let _ = Configuration::new("http://localhost").with_rate_limit(f64::NAN, 1);
+}
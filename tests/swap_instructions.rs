@@ -0,0 +1,181 @@
+use common::{active_table_account, table_account, MockFetcher};
+use juno::{
+    Configuration, DecompiledVersionedTx, Error, JupiterClient, RetryPolicy, SwapInstructionSet,
+    SwapOptions, SwapRoute,
+};
+use serde_json::Value;
+use solana_program::address_lookup_table::state::LookupTableMeta;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const QUOTE: &str = include_str!("fixtures/quote.json");
+const SWAP_INSTRUCTIONS: &str = include_str!("fixtures/swap_instructions.json");
+
+const USER: Pubkey = pubkey!("7v91N7iZ9mNicL8WfG6cgSCKyRXydQjLh6UYBWwm6y1Q");
+const COMPUTE_BUDGET: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+const JUPITER: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+const TOKEN: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const SYSTEM: Pubkey = pubkey!("11111111111111111111111111111111");
+const TABLES: [Pubkey; 2] = [
+    pubkey!("GxS6FiQ3mNnAar9HGQ6mxP7t6FcwmHkU7peSeQDUHmpN"),
+    pubkey!("2immgwYNHBbyVQKVGCEkgWpi53bLwWNRMB5G2nbgYV17"),
+];
+
+async fn server(body: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/swap-instructions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn fetcher() -> MockFetcher {
+    MockFetcher::new(TABLES.iter().map(|table| {
+        (
+            *table,
+            active_table_account(&[
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ]),
+        )
+    }))
+}
+
+async fn instruction_set(
+    server: &MockServer,
+    fetcher: &MockFetcher,
+) -> juno::Result<SwapInstructionSet> {
+    let configuration = Configuration::new(server.uri()).with_retry_policy(RetryPolicy::none());
+    let route: SwapRoute = serde_json::from_str(QUOTE).unwrap();
+    JupiterClient::with_configuration(configuration)
+        .get_swap_instruction_set(route, USER, &SwapOptions::default(), fetcher)
+        .await
+}
+
+#[tokio::test]
+async fn captured_swap_instructions_are_decoded() {
+    let server = server(SWAP_INSTRUCTIONS).await;
+    let fetcher = fetcher();
+
+    let set = instruction_set(&server, &fetcher).await.unwrap();
+
+    assert!(set.token_ledger_instruction.is_none());
+    assert_eq!(set.compute_budget_instructions.len(), 2);
+    assert!(set
+        .compute_budget_instructions
+        .iter()
+        .all(|ix| ix.program_id == COMPUTE_BUDGET));
+    // SetComputeUnitLimit(1_400_000)
+    assert_eq!(
+        set.compute_budget_instructions[0].data,
+        [2, 0xc0, 0x5c, 0x15, 0]
+    );
+    assert_eq!(set.setup_instructions.len(), 3);
+    assert_eq!(set.swap_instruction.program_id, JUPITER);
+    assert_eq!(set.swap_instruction.accounts.len(), 6);
+    assert!(set.swap_instruction.accounts[1].is_signer);
+    assert!(!set.swap_instruction.accounts[1].is_writable);
+    let cleanup = set.cleanup_instruction.as_ref().unwrap();
+    assert_eq!(
+        (cleanup.program_id, cleanup.data.as_slice()),
+        (TOKEN, &[9][..])
+    );
+    assert_eq!(set.other_instructions.len(), 1);
+    assert_eq!(set.other_instructions[0].program_id, SYSTEM);
+    let table_keys = set
+        .lookup_tables
+        .iter()
+        .map(|table| table.key)
+        .collect::<Vec<_>>();
+    assert_eq!(table_keys, TABLES);
+    assert!(set
+        .lookup_tables
+        .iter()
+        .all(|table| table.addresses.len() == 3));
+    assert_eq!(fetcher.fetched(), TABLES);
+}
+
+#[tokio::test]
+async fn invalid_instruction_data_is_reported() {
+    let mut body = serde_json::from_str::<Value>(SWAP_INSTRUCTIONS).unwrap();
+    body["swapInstruction"]["data"] = "not base64!".into();
+    let server = server(&body.to_string()).await;
+
+    let error = instruction_set(&server, &fetcher()).await.unwrap_err();
+
+    assert!(matches!(error, Error::Base64Decode(_)), "{error:?}");
+}
+
+#[tokio::test]
+async fn decompiled_instructions_follow_the_transaction_order() {
+    let server = server(SWAP_INSTRUCTIONS).await;
+    let set = instruction_set(&server, &fetcher()).await.unwrap();
+    let expected = set
+        .compute_budget_instructions
+        .iter()
+        .chain(&set.setup_instructions)
+        .chain([&set.swap_instruction])
+        .chain(&set.cleanup_instruction)
+        .chain(&set.other_instructions)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let decompiled = DecompiledVersionedTx::from(set);
+
+    assert_eq!(decompiled.instructions, expected);
+    let program_ids = decompiled
+        .instructions
+        .iter()
+        .map(|ix| ix.program_id)
+        .collect::<Vec<_>>();
+    assert_eq!(program_ids.first(), Some(&COMPUTE_BUDGET));
+    assert_eq!(program_ids[5], JUPITER);
+    assert_eq!(program_ids.last(), Some(&SYSTEM));
+    let table_keys = decompiled
+        .lookup_tables
+        .unwrap()
+        .iter()
+        .map(|table| table.key)
+        .collect::<Vec<_>>();
+    assert_eq!(table_keys, TABLES);
+}
+
+#[tokio::test]
+async fn swaps_without_lookup_tables_are_decompiled_without_tables() {
+    let mut body = serde_json::from_str::<Value>(SWAP_INSTRUCTIONS).unwrap();
+    body.as_object_mut()
+        .unwrap()
+        .remove("addressLookupTableAddresses");
+    let server = server(&body.to_string()).await;
+    let fetcher = fetcher();
+
+    let set = instruction_set(&server, &fetcher).await.unwrap();
+
+    assert!(fetcher.fetched().is_empty());
+    assert!(DecompiledVersionedTx::from(set).lookup_tables.is_none());
+}
+
+#[tokio::test]
+async fn deactivating_lookup_tables_are_rejected() {
+    let server = server(SWAP_INSTRUCTIONS).await;
+    let fetcher = fetcher();
+    let meta = LookupTableMeta {
+        deactivation_slot: 299_283_700,
+        ..LookupTableMeta::default()
+    };
+    fetcher.set_account(TABLES[1], table_account(meta, &[Pubkey::new_unique()]));
+
+    let error = instruction_set(&server, &fetcher).await.unwrap_err();
+
+    assert!(
+        matches!(error, Error::LookupTableDeactivated(key) if key == TABLES[1]),
+        "{error:?}"
+    );
+}
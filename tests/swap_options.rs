@@ -0,0 +1,84 @@
+use common::MockFetcher;
+use juno::{Configuration, JupiterClient, RetryPolicy, SwapOptions, SwapRoute};
+use serde_json::{json, Map, Value};
+use solana_sdk::pubkey::Pubkey;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const QUOTE: &str = include_str!("fixtures/quote.json");
+
+/// Body of the swap-instructions request sent with `options`, without the quote
+async fn sent_body(user: Pubkey, options: &SwapOptions) -> Map<String, Value> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/swap-instructions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "swapInstruction": {
+                "programId": Pubkey::new_unique().to_string(),
+                "accounts": [],
+                "data": "AQID",
+            },
+        })))
+        .mount(&server)
+        .await;
+    let route: SwapRoute = serde_json::from_str(QUOTE).unwrap();
+
+    JupiterClient::with_configuration(
+        Configuration::new(server.uri()).with_retry_policy(RetryPolicy::none()),
+    )
+    .get_swap_instruction_set(route, user, options, &MockFetcher::default())
+    .await
+    .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let mut body = serde_json::from_slice::<Map<String, Value>>(&requests[0].body).unwrap();
+    assert!(body.remove("quoteResponse").is_some());
+    body
+}
+
+#[tokio::test]
+async fn swap_options_are_sent_in_the_body() {
+    let user = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let options = SwapOptions::default()
+        .compute_unit_price_micro_lamports(1_000)
+        .prioritization_fee_lamports(50_000)
+        .dynamic_compute_unit_limit(true)
+        .use_shared_accounts(false)
+        .destination_token_account(destination)
+        .use_token_ledger(true);
+
+    let body = sent_body(user, &options).await;
+
+    assert_eq!(
+        Value::Object(body),
+        json!({
+            "userPublicKey": user.to_string(),
+            "wrapAndUnwrapSol": false,
+            "asLegacyTransaction": false,
+            "destinationTokenAccount": destination.to_string(),
+            "computeUnitPriceMicroLamports": 1_000,
+            "prioritizationFeeLamports": 50_000,
+            "dynamicComputeUnitLimit": true,
+            "useSharedAccounts": false,
+            "useTokenLedger": true,
+        })
+    );
+}
+
+#[tokio::test]
+async fn unset_swap_options_are_omitted() {
+    let user = Pubkey::new_unique();
+
+    let body = sent_body(user, &SwapOptions::default()).await;
+
+    assert!(body.values().all(|value| !value.is_null()), "{body:?}");
+    let mut names = body.keys().map(String::as_str).collect::<Vec<_>>();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        ["asLegacyTransaction", "userPublicKey", "wrapAndUnwrapSol"]
+    );
+}
@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+
+use juno::models::SwapPostRequest;
+use juno::reexports::{Decimal, Pubkey};
+use juno::{SwapOptions, SwapRoute};
+use solana_sdk::pubkey;
+
+const QUOTE: &str = include_str!("fixtures/quote.json");
+
+fn quote_json() -> serde_json::Value {
+    serde_json::from_str(QUOTE).unwrap()
+}
+
+#[test]
+fn quote_is_deserialized_into_typed_fields() {
+    let route: SwapRoute = serde_json::from_str(QUOTE).unwrap();
+
+    assert_eq!(
+        route.input_mint,
+        pubkey!("USDH1SM1ojwWUga67PGrgFWUHibbjqMvuMaDkRJTgkX")
+    );
+    assert_eq!(
+        route.price_impact_pct,
+        "0.0004186291736".parse::<Decimal>().unwrap()
+    );
+    let swap_info = &route.route_plan[1].swap_info;
+    assert_eq!(
+        swap_info.amm_key,
+        pubkey!("4kAxRrTXm6EqC1wpnH2WpV9G7YCjCBq1hdbWFVtUhMbm")
+    );
+    assert_eq!(swap_info.in_amount, 999_871);
+    assert_eq!(swap_info.fee_amount, 2_999);
+}
+
+#[test]
+fn scientific_price_impact_is_accepted() {
+    let mut quote = quote_json();
+    quote["priceImpactPct"] = "4.5e-7".into();
+
+    let route: SwapRoute = serde_json::from_value(quote).unwrap();
+
+    assert_eq!(
+        route.price_impact_pct,
+        "0.00000045".parse::<Decimal>().unwrap()
+    );
+}
+
+#[test]
+fn malformed_values_are_rejected_at_parse_time() {
+    let cases = [
+        ("/outputMint", "not-a-pubkey"),
+        ("/priceImpactPct", "12%"),
+        ("/routePlan/0/swapInfo/ammKey", "bad"),
+        ("/routePlan/0/swapInfo/feeAmount", "-1"),
+    ];
+    for (pointer, malformed) in cases {
+        let mut quote = quote_json();
+        *quote.pointer_mut(pointer).unwrap() = malformed.into();
+        assert!(
+            serde_json::from_value::<SwapRoute>(quote).is_err(),
+            "{malformed} accepted for {pointer}"
+        );
+    }
+}
+
+const CAPTURED_QUOTES: [&str; 5] = [
+    QUOTE,
+    include_str!("fixtures/quote_with_unknown_fields.json"),
+    include_str!("fixtures/quote_with_interleaved_fields.json"),
+    include_str!("fixtures/quote_with_scientific_price_impact.json"),
+    include_str!("fixtures/quote_without_platform_fee.json"),
+];
+
+#[test]
+fn captured_quotes_round_trip_byte_for_byte() {
+    for captured in CAPTURED_QUOTES {
+        let captured = captured.trim_end();
+        let route: SwapRoute = serde_json::from_str(captured).unwrap();
+
+        assert_eq!(serde_json::to_string(&route).unwrap(), captured);
+    }
+}
+
+#[test]
+fn captured_quotes_are_sent_to_swap_byte_for_byte() {
+    for captured in CAPTURED_QUOTES {
+        let captured = captured.trim_end();
+        let route: SwapRoute = serde_json::from_str(captured).unwrap();
+        let user = Pubkey::new_unique();
+
+        let request = SwapPostRequest::new(route, user, &SwapOptions::default());
+
+        let body = serde_json::to_string(&request).unwrap();
+        assert!(
+            body.starts_with(&format!(r#"{{"quoteResponse":{captured},"userPublicKey""#)),
+            "{body}"
+        );
+    }
+}
+
+#[test]
+fn edge_case_quotes_are_typed() {
+    let interleaved: SwapRoute =
+        serde_json::from_str(include_str!("fixtures/quote_with_interleaved_fields.json")).unwrap();
+    assert_eq!(interleaved.extra["computedAutoSlippage"], 100);
+    assert_eq!(interleaved.route_plan[0].extra["bps"], 10_000);
+    assert_eq!(interleaved.route_plan[0].swap_info.extra["poolVersion"], 2);
+
+    let scientific: SwapRoute = serde_json::from_str(include_str!(
+        "fixtures/quote_with_scientific_price_impact.json"
+    ))
+    .unwrap();
+    assert_eq!(
+        scientific.price_impact_pct,
+        "0.00001".parse::<Decimal>().unwrap()
+    );
+
+    let without_fee: SwapRoute =
+        serde_json::from_str(include_str!("fixtures/quote_without_platform_fee.json")).unwrap();
+    assert!(without_fee.platform_fee.is_none());
+}
+
+#[test]
+fn unknown_fields_are_echoed_back_to_swap() {
+    let captured = include_str!("fixtures/quote_with_unknown_fields.json").trim_end();
+    let route: SwapRoute = serde_json::from_str(captured).unwrap();
+    assert_eq!(route.extra["computedAutoSlippage"], 50);
+    assert_eq!(route.route_plan[0].extra["bps"], 10_000);
+    assert_eq!(route.route_plan[0].swap_info.extra["poolVersion"], 2);
+
+    let request = SwapPostRequest::new(route, Pubkey::new_unique(), &SwapOptions::default());
+    let body = serde_json::to_value(&request).unwrap();
+
+    assert_eq!(
+        body["quoteResponse"],
+        serde_json::from_str::<serde_json::Value>(captured).unwrap()
+    );
+}
+
+#[test]
+fn fee_breakdown_totals_lp_and_platform_fees_per_mint() {
+    let route: SwapRoute =
+        serde_json::from_str(include_str!("fixtures/quote_with_platform_fee.json")).unwrap();
+    let sol = pubkey!("So11111111111111111111111111111111111111112");
+    let usdc = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+    let usdh = pubkey!("USDH1SM1ojwWUga67PGrgFWUHibbjqMvuMaDkRJTgkX");
+
+    let fees = route.fee_breakdown();
+
+    assert_eq!(route.platform_fee.as_ref().unwrap().fee_bps, 20);
+    assert_eq!(fees.lp_fees, BTreeMap::from([(sol, 1_724), (usdc, 1_619)]));
+    assert_eq!(fees.platform_fee, Some((usdh, 32_364)));
+    assert_eq!(
+        fees.total_per_mint(),
+        BTreeMap::from([(sol, 1_724), (usdc, 1_619), (usdh, 32_364)])
+    );
+}
+
+#[test]
+fn mutated_routes_are_serialized_from_their_fields() {
+    let captured = include_str!("fixtures/quote_with_unknown_fields.json").trim_end();
+    let mut route: SwapRoute = serde_json::from_str(captured).unwrap();
+    route.slippage_bps = 300;
+    route.route_plan[0].percent = 50;
+
+    let serialized = serde_json::to_value(&route).unwrap();
+
+    let mut expected = serde_json::from_str::<serde_json::Value>(captured).unwrap();
+    expected["slippageBps"] = 300.into();
+    expected["routePlan"][0]["percent"] = 50.into();
+    assert_eq!(serialized, expected);
+}
+
+#[test]
+fn routes_built_by_hand_are_serialized() {
+    let route = SwapRoute {
+        in_amount: 1_000,
+        out_amount: 990,
+        context_slot: 42,
+        ..SwapRoute::default()
+    };
+
+    let serialized = serde_json::to_value(&route).unwrap();
+
+    assert_eq!(serialized["inAmount"], "1000");
+    assert_eq!(serialized["outAmount"], "990");
+    assert_eq!(serialized["contextSlot"], 42);
+    let parsed = serde_json::from_value::<SwapRoute>(serialized.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), serialized);
+}
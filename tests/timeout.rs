@@ -0,0 +1,87 @@
+use std::time::{Duration, Instant};
+
+use juno::{Configuration, Error, JupiterClient, QuoteRequest, RetryPolicy};
+use solana_sdk::pubkey::Pubkey;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const QUOTE: &str = include_str!("fixtures/quote.json");
+
+fn quote_request() -> QuoteRequest {
+    QuoteRequest::new(Pubkey::new_unique(), Pubkey::new_unique(), 1_000_000)
+}
+
+#[tokio::test]
+async fn configured_timeout_bounds_every_attempt() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(QUOTE)
+                .set_delay(Duration::from_secs(5)),
+        )
+        .mount(&server)
+        .await;
+    let client = JupiterClient::with_configuration(
+        Configuration::new(server.uri())
+            .with_retry_policy(RetryPolicy::none())
+            .with_timeout(Some(Duration::from_millis(100))),
+    );
+
+    let start = Instant::now();
+    let err = client.get_quote(&quote_request()).await.unwrap_err();
+
+    assert!(matches!(err, Error::Timeout), "{err:?}");
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn per_call_deadline_overrides_configured_timeout() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(QUOTE)
+                .set_delay(Duration::from_millis(300)),
+        )
+        .mount(&server)
+        .await;
+    let client = JupiterClient::with_configuration(
+        Configuration::new(server.uri()).with_timeout(Some(Duration::from_millis(50))),
+    );
+
+    let quote = client
+        .get_quote(&quote_request().timeout(Duration::from_secs(2)))
+        .await
+        .unwrap();
+
+    assert_eq!(quote.in_amount, 1_000_000);
+}
+
+#[tokio::test]
+async fn per_call_deadline_includes_retries() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    let client = JupiterClient::with_configuration(
+        Configuration::new(server.uri()).with_retry_policy(RetryPolicy {
+            max_attempts: u32::MAX,
+            initial_backoff: Duration::from_millis(50),
+            ..Default::default()
+        }),
+    );
+
+    let start = Instant::now();
+    let err = client
+        .get_quote(&quote_request().timeout(Duration::from_millis(200)))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::Timeout), "{err:?}");
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
@@ -0,0 +1,127 @@
+use juno::{DecompiledVersionedTx, Error, TxComposer};
+use solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
+use solana_sdk::compute_budget::{self, ComputeBudgetInstruction};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::signer::SignerError;
+
+fn instruction(signer: &Pubkey, accounts: &[Pubkey]) -> Instruction {
+    let mut metas = vec![AccountMeta::new(*signer, true)];
+    metas.extend(
+        accounts
+            .iter()
+            .map(|account| AccountMeta::new(*account, false)),
+    );
+    Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3], metas)
+}
+
+fn swap(user: &Pubkey, table: AddressLookupTableAccount) -> DecompiledVersionedTx {
+    DecompiledVersionedTx {
+        instructions: vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            ComputeBudgetInstruction::set_compute_unit_price(1_000),
+            instruction(user, &table.addresses),
+        ],
+        lookup_tables: Some(vec![table]),
+    }
+}
+
+#[test]
+fn compute_budget_instructions_are_merged_first() {
+    let user = Pubkey::new_unique();
+    let pre = instruction(&user, &[]);
+    let post = instruction(&user, &[]);
+    let table = AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: vec![Pubkey::new_unique()],
+    };
+    let composer = TxComposer::new(user)
+        .pre_instructions([
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+            pre.clone(),
+        ])
+        .swap(swap(&user, table))
+        .post_instructions([
+            post.clone(),
+            ComputeBudgetInstruction::set_compute_unit_price(5_000),
+        ]);
+
+    let instructions = composer.instructions();
+    assert_eq!(
+        instructions[..2],
+        [
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            ComputeBudgetInstruction::set_compute_unit_price(5_000),
+        ]
+    );
+    assert_eq!(instructions.len(), 5);
+    assert_eq!(instructions[2], pre);
+    assert_eq!(instructions[4], post);
+    assert_eq!(
+        instructions
+            .iter()
+            .filter(|ix| ix.program_id == compute_budget::id())
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn lookup_tables_are_merged() {
+    let user = Pubkey::new_unique();
+    let shared_key = Pubkey::new_unique();
+    let swap_table = AddressLookupTableAccount {
+        key: shared_key,
+        addresses: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+    };
+    let stale_copy = AddressLookupTableAccount {
+        key: shared_key,
+        addresses: swap_table.addresses[..1].to_vec(),
+    };
+    let caller_table = AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: vec![Pubkey::new_unique()],
+    };
+    let composer = TxComposer::new(user)
+        .lookup_tables([stale_copy, caller_table.clone()])
+        .pre_instructions([instruction(&user, &caller_table.addresses)])
+        .swap(swap(&user, swap_table.clone()));
+
+    assert_eq!(
+        composer.merged_lookup_tables(),
+        vec![swap_table, caller_table]
+    );
+    let message = composer.compile(Hash::new_unique()).unwrap();
+    assert_eq!(message.address_table_lookups().unwrap().len(), 2);
+}
+
+#[test]
+fn transactions_can_have_a_separate_fee_payer() {
+    let (fee_payer, user) = (Keypair::new(), Keypair::new());
+    let table = AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: vec![Pubkey::new_unique()],
+    };
+    let composer = TxComposer::new(fee_payer.pubkey()).swap(swap(&user.pubkey(), table));
+    let blockhash = Hash::new_unique();
+
+    let unsigned = composer.build_unsigned(blockhash).unwrap();
+    assert_eq!(unsigned.signatures, vec![Signature::default(); 2]);
+    assert_eq!(
+        unsigned.message.static_account_keys()[0],
+        fee_payer.pubkey()
+    );
+
+    let signed = composer
+        .build_signed(blockhash, &[&user, &fee_payer])
+        .unwrap();
+    assert_eq!(signed.message, unsigned.message);
+    assert!(signed.verify_with_results().into_iter().all(|valid| valid));
+
+    assert!(matches!(
+        composer.build_signed(blockhash, &[&user]),
+        Err(Error::SolanaSigner(SignerError::NotEnoughSigners))
+    ));
+}
@@ -0,0 +1,108 @@
+use juno::tx_size::{LEGACY_TX_ACCOUNT_LOCKS, MAX_TX_ACCOUNT_LOCKS};
+use juno::{DecompiledVersionedTx, TxComposer};
+use solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::pubkey::Pubkey;
+
+fn keys(count: usize) -> Vec<Pubkey> {
+    (0..count).map(|_| Pubkey::new_unique()).collect()
+}
+
+fn instruction(signer: &Pubkey, accounts: &[Pubkey], data_len: usize) -> Instruction {
+    let mut metas = vec![AccountMeta::new(*signer, true)];
+    metas.extend(accounts.iter().enumerate().map(|(i, account)| {
+        if i % 2 == 0 {
+            AccountMeta::new(*account, false)
+        } else {
+            AccountMeta::new_readonly(*account, false)
+        }
+    }));
+    Instruction::new_with_bytes(Pubkey::new_unique(), &vec![7; data_len], metas)
+}
+
+/// Swap over `accounts` accounts, `in_tables` of them available in a lookup table
+fn composer(
+    user: &Pubkey,
+    accounts: usize,
+    in_tables: usize,
+    caller_accounts: &[Pubkey],
+) -> TxComposer {
+    let swap_accounts = keys(accounts);
+    let table = AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: swap_accounts[..in_tables].to_vec(),
+    };
+    TxComposer::new(*user)
+        .pre_instructions([instruction(user, caller_accounts, 40)])
+        .swap(DecompiledVersionedTx {
+            instructions: vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                instruction(user, &swap_accounts, 120),
+            ],
+            lookup_tables: Some(vec![table]),
+        })
+        .post_instructions([instruction(user, &caller_accounts[..2], 8)])
+}
+
+fn serialized_size(composer: &TxComposer) -> usize {
+    let tx = composer.build_unsigned(Hash::new_unique()).unwrap();
+    bincode::serialized_size(&tx).unwrap() as usize
+}
+
+#[test]
+fn estimate_matches_the_compiled_transaction() {
+    let user = Pubkey::new_unique();
+    for (accounts, in_tables) in [(0, 0), (10, 0), (20, 15), (40, 40), (150, 140)] {
+        let composer = composer(&user, accounts, in_tables, &keys(4));
+
+        let estimate = composer.estimate(MAX_TX_ACCOUNT_LOCKS);
+        let message = composer.compile(Hash::new_unique()).unwrap();
+        assert_eq!(
+            estimate.size,
+            serialized_size(&composer),
+            "{accounts} accounts"
+        );
+        assert_eq!(
+            estimate.static_accounts,
+            message.static_account_keys(),
+            "{accounts} accounts"
+        );
+        assert_eq!(estimate.lookup_account_count, in_tables);
+        assert_eq!(estimate.signer_count, 1);
+        assert_eq!(
+            estimate.remaining_bytes,
+            PACKET_DATA_SIZE as isize - estimate.size as isize
+        );
+        // Caller accounts, user and the 4 programs
+        assert_eq!(estimate.account_count, accounts + 4 + 1 + 4);
+        assert_eq!(estimate.swap_account_count, accounts + 1);
+    }
+}
+
+#[test]
+fn suggested_max_accounts_makes_the_swap_fit() {
+    let user = Pubkey::new_unique();
+    let caller_accounts = keys(20);
+
+    let too_many_static = composer(&user, 40, 0, &caller_accounts).estimate(MAX_TX_ACCOUNT_LOCKS);
+    assert!(!too_many_static.fits());
+    let suggested = usize::from(too_many_static.suggested_max_accounts);
+    assert!(suggested < 40);
+    let retry = composer(&user, suggested - 1, 0, &caller_accounts).estimate(MAX_TX_ACCOUNT_LOCKS);
+    assert!(retry.fits(), "{retry:?}");
+
+    let caller_accounts = keys(4);
+    let too_many_locks =
+        composer(&user, 60, 60, &caller_accounts).estimate(LEGACY_TX_ACCOUNT_LOCKS);
+    assert!(too_many_locks.remaining_bytes > 0);
+    assert!(!too_many_locks.fits());
+    let suggested = usize::from(too_many_locks.suggested_max_accounts);
+    // The swap program counts as one of the swap accounts
+    let retry = composer(&user, suggested - 1, suggested - 1, &caller_accounts)
+        .estimate(LEGACY_TX_ACCOUNT_LOCKS);
+    assert!(retry.fits(), "{retry:?}");
+    assert_eq!(retry.account_count, LEGACY_TX_ACCOUNT_LOCKS);
+}